@@ -0,0 +1,190 @@
+use std::{path::PathBuf, time::Duration};
+
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://atcoder.jp";
+pub const BASE_URL_ENV: &str = "ATCODER_BASE_URL";
+pub const DEFAULT_CODEFORCES_BASE_URL: &str = "https://codeforces.com";
+pub const CODEFORCES_BASE_URL_ENV: &str = "CODEFORCES_BASE_URL";
+pub const DEFAULT_YUKICODER_BASE_URL: &str = "https://yukicoder.me";
+pub const YUKICODER_BASE_URL_ENV: &str = "YUKICODER_BASE_URL";
+/// `login` で端末での入力の代わりに使うユーザー名とパスワード
+pub const USERNAME_ENV: &str = "ATCODER_USERNAME";
+pub const PASSWORD_ENV: &str = "ATCODER_PASSWORD";
+
+/// 設定ファイル・環境変数・コマンドライン引数から決まる、サブコマンドに共通の設定
+pub struct Config {
+    /// ジャッジの URL (末尾の `/` は含まない)
+    pub base_url: String,
+    /// Codeforces の URL (末尾の `/` は含まない)
+    pub codeforces_base_url: String,
+    /// yukicoder の URL (末尾の `/` は含まない)
+    pub yukicoder_base_url: String,
+    /// 同時に取得する問題ページの数
+    pub fetch_jobs: usize,
+    /// 1 リクエストあたりのタイムアウト
+    pub timeout: Duration,
+    /// 一時的なエラーのときに再試行する回数
+    pub retries: u32,
+    /// リクエストの間に空ける最小の間隔
+    pub interval: Duration,
+    /// リクエストを送らず、キャッシュした HTML だけを使う
+    pub offline: bool,
+    /// 問題文の言語
+    pub lang: Lang,
+    /// `--profile` で指定したアカウントのプロファイル
+    pub profile: Option<String>,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            codeforces_base_url: DEFAULT_CODEFORCES_BASE_URL.to_string(),
+            yukicoder_base_url: DEFAULT_YUKICODER_BASE_URL.to_string(),
+            fetch_jobs: 4,
+            timeout: Duration::from_secs(10),
+            retries: 3,
+            interval: Duration::from_millis(200),
+            offline: false,
+            lang: Lang::Auto,
+            profile: None,
+        }
+    }
+}
+impl Config {
+    /// 設定ファイル (`~/.atcoder-create-contest-dir/config.toml`) と環境変数を反映した設定
+    ///
+    /// `profile` があれば `profiles/{profile}/config.toml` の設定で上書きする
+    pub fn load(profile: Option<&str>) -> Result<Config, String> {
+        let mut config = Config {
+            profile: profile.map(|profile| profile.to_string()),
+            ..Config::default()
+        };
+
+        let mut paths = vec![data_dir().join("config.toml")];
+        if let Some(profile) = profile {
+            if !PROFILE_NAME_REGEX.is_match(profile) {
+                return Err(format!("Invalid Profile Name `{}`", profile));
+            }
+            paths.push(profile_dir_of(profile).join("config.toml"));
+        }
+        for path in paths.iter().filter(|path| path.is_file()) {
+            let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            let file: ConfigFile = toml::from_str(&content)
+                .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+            if let Some(base_url) = file.base_url {
+                config.base_url = base_url;
+            }
+            if let Some(base_url) = file.codeforces_base_url {
+                config.codeforces_base_url = base_url;
+            }
+            if let Some(base_url) = file.yukicoder_base_url {
+                config.yukicoder_base_url = base_url;
+            }
+        }
+        if let Ok(base_url) = std::env::var(BASE_URL_ENV) {
+            config.base_url = base_url;
+        }
+        if let Ok(base_url) = std::env::var(CODEFORCES_BASE_URL_ENV) {
+            config.codeforces_base_url = base_url;
+        }
+        if let Ok(base_url) = std::env::var(YUKICODER_BASE_URL_ENV) {
+            config.yukicoder_base_url = base_url;
+        }
+        config.base_url = config.base_url.trim_end_matches('/').to_string();
+        config.codeforces_base_url = config.codeforces_base_url.trim_end_matches('/').to_string();
+        config.yukicoder_base_url = config.yukicoder_base_url.trim_end_matches('/').to_string();
+
+        Ok(config)
+    }
+
+    /// `base_url` から scheme を除いたもの (例: `atcoder.jp`)
+    pub fn base_host(&self) -> &str {
+        host_of(&self.base_url)
+    }
+
+    /// `codeforces_base_url` から scheme を除いたもの (例: `codeforces.com`)
+    pub fn codeforces_host(&self) -> &str {
+        host_of(&self.codeforces_base_url)
+    }
+
+    /// `yukicoder_base_url` から scheme を除いたもの (例: `yukicoder.me`)
+    pub fn yukicoder_host(&self) -> &str {
+        host_of(&self.yukicoder_base_url)
+    }
+}
+
+fn host_of(url: &str) -> &str {
+    url.split_once("://").map_or(url, |(_, host)| host)
+}
+
+/// 問題文の言語 (`Auto` は日本語があれば日本語、なければ英語)
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lang {
+    Ja,
+    En,
+    Auto,
+}
+impl Lang {
+    pub fn from_name(name: &str) -> Option<Lang> {
+        match name.to_lowercase().as_str() {
+            "ja" => Some(Lang::Ja),
+            "en" => Some(Lang::En),
+            "auto" => Some(Lang::Auto),
+            _ => None,
+        }
+    }
+
+    /// ページを取得するときにつける `lang` パラメーターの値
+    pub fn query_value<'a>(&self) -> Option<&'a str> {
+        match *self {
+            Lang::Ja => Some("ja"),
+            Lang::En => Some("en"),
+            Lang::Auto => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    base_url: Option<String>,
+    codeforces_base_url: Option<String>,
+    yukicoder_base_url: Option<String>,
+}
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+
+pub fn init_config(config: Config) {
+    CONFIG
+        .set(config)
+        .unwrap_or_else(|_| panic!("Config is Already Initialized"));
+}
+
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// cookie などを保存するディレクトリ
+pub fn data_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap()
+        .join(".atcoder-create-contest-dir")
+}
+
+static PROFILE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z0-9_-]+$").unwrap());
+
+fn profile_dir_of(profile: &str) -> PathBuf {
+    data_dir().join("profiles").join(profile)
+}
+
+/// cookie などアカウントごとのファイルを保存するディレクトリ
+///
+/// `--profile NAME` のときは `profiles/NAME/`、なければ `data_dir()` そのもの
+pub fn profile_dir() -> PathBuf {
+    match &config().profile {
+        Some(profile) => profile_dir_of(profile),
+        None => data_dir(),
+    }
+}
@@ -0,0 +1,299 @@
+use std::{convert::TryFrom, path::PathBuf};
+
+use aes_gcm::{
+    aead::{Aead, NewAead},
+    Aes256Gcm, Key, Nonce,
+};
+use hmac::Hmac;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{config::profile_dir, ErrorMessages};
+
+pub const COOKIE_FILE_NAME: &str = "cookie";
+/// 暗号化した cookie を読み書きするときのパスフレーズ (なければ端末で入力する)
+pub const PASSPHRASE_ENV: &str = "ATCODER_COOKIE_PASSPHRASE";
+
+const PBKDF2_ROUNDS: u32 = 100_000;
+
+/// `REVEL_SESSION` の値に含まれる有効期限 (`_TS:1700000000`)
+static SESSION_TS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"_TS(?::|%3A|%3a)([0-9]+)").unwrap());
+
+/// 保存する cookie 1 つ
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    /// 有効期限 (RFC 3339、セッション cookie なら `None`)
+    pub expires: Option<String>,
+}
+impl StoredCookie {
+    fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let expires = chrono::DateTime::parse_from_rfc3339(self.expires.as_ref()?).ok()?;
+        Some(expires.with_timezone(&chrono::Utc))
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at()
+            .is_some_and(|expires| expires <= chrono::Utc::now())
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        let domain = self.domain.trim_start_matches('.');
+        host == domain || host.ends_with(&format!(".{}", domain))
+    }
+}
+
+/// `~/.atcoder-create-contest-dir/cookie` (プロファイルごとに `profiles/NAME/cookie`) に保存する cookie の一覧
+#[derive(Serialize, Deserialize, Default)]
+pub struct CookieJar {
+    #[serde(default)]
+    pub cookies: Vec<StoredCookie>,
+}
+
+/// cookie ファイルの中身 (`encrypted` があれば暗号化されている)
+#[derive(Serialize, Deserialize)]
+struct CookieFile {
+    cookies: Option<Vec<StoredCookie>>,
+    encrypted: Option<EncryptedCookies>,
+}
+
+/// パスフレーズから PBKDF2 で作った鍵で AES-256-GCM で暗号化した `CookieJar`
+#[derive(Serialize, Deserialize)]
+struct EncryptedCookies {
+    salt: String,
+    nonce: String,
+    data: String,
+}
+
+impl CookieJar {
+    /// レスポンスの `Set-Cookie` から作る (`Domain` がなければ `host` の cookie とする)
+    pub fn from_cookies<'a>(
+        cookies: impl Iterator<Item = reqwest::cookie::Cookie<'a>>,
+        host: &str,
+    ) -> Self {
+        let cookies = cookies
+            .map(|cookie| {
+                let expires = match (cookie.max_age(), cookie.expires()) {
+                    (Some(max_age), _) => chrono::Duration::from_std(max_age)
+                        .ok()
+                        .map(|max_age| chrono::Utc::now() + max_age),
+                    (None, Some(expires)) => Some(expires.into()),
+                    (None, None) => None,
+                };
+                StoredCookie {
+                    name: cookie.name().to_string(),
+                    value: cookie.value().to_string(),
+                    domain: cookie.domain().unwrap_or(host_name(host)).to_string(),
+                    path: cookie.path().unwrap_or("/").to_string(),
+                    expires: expires.map(|expires| expires.to_rfc3339()),
+                }
+            })
+            .collect();
+        CookieJar { cookies }
+    }
+
+    /// `host` に送る `Cookie` ヘッダー (期限切れのものは除く)
+    ///
+    /// すべての cookie を `; ` でつないだ 1 つのヘッダーにする
+    pub fn header_value(&self, host: &str) -> Option<HeaderValue> {
+        let host = host_name(host);
+        let cookies = self
+            .cookies
+            .iter()
+            .filter(|cookie| cookie.matches(host) && !cookie.is_expired())
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect::<Vec<String>>();
+        if cookies.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&cookies.join("; ")).ok()
+    }
+
+    /// `REVEL_SESSION` の有効期限
+    ///
+    /// `Expires` が保存されていなければ値に含まれる `_TS` から読み取る
+    pub fn session_expiry(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let session = self.cookies.iter().find(|c| c.name == "REVEL_SESSION")?;
+        session.expires_at().or_else(|| {
+            let timestamp = SESSION_TS_REGEX.captures(&session.value)?[1].parse().ok()?;
+            chrono::TimeZone::timestamp_opt(&chrono::Utc, timestamp, 0).single()
+        })
+    }
+
+    pub fn path() -> PathBuf {
+        profile_dir().join(COOKIE_FILE_NAME)
+    }
+
+    /// 保存した cookie を読み込む (保存していなければ `Ok(None)`)
+    ///
+    /// 暗号化されている場合は `ATCODER_COOKIE_PASSPHRASE` か端末で入力したパスフレーズで復号する
+    /// 以前の `name=value;name=value` の形式のファイルも `host` の cookie として読み込む
+    pub fn load(host: &str) -> Result<Option<CookieJar>, String> {
+        let path = Self::path();
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path).map_err(|_e| ErrorMessages::FailedGet)?;
+
+        match toml::from_str::<CookieFile>(&content) {
+            Ok(CookieFile {
+                encrypted: Some(encrypted),
+                ..
+            }) => {
+                let passphrase = read_passphrase("cookie passphrase: ")?;
+                decrypt(&encrypted, &passphrase).map(Some)
+            }
+            Ok(CookieFile {
+                cookies: Some(cookies),
+                ..
+            }) => Ok(Some(CookieJar { cookies })),
+            _ => Ok(Some(CookieJar::from_legacy(&content, host))),
+        }
+    }
+
+    fn from_legacy(content: &str, host: &str) -> Self {
+        let cookies = content
+            .lines()
+            .flat_map(|line| line.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .map(|(name, value)| StoredCookie {
+                name: name.to_string(),
+                value: value.to_string(),
+                domain: host_name(host).to_string(),
+                path: "/".to_string(),
+                expires: None,
+            })
+            .collect();
+        CookieJar { cookies }
+    }
+
+    /// 保存した cookie を削除する (保存していなければ `Ok(false)`)
+    pub fn remove() -> Result<bool, String> {
+        let path = Self::path();
+        if !path.is_file() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path).map_err(|_e| ErrorMessages::FailedRemoveFile)?;
+        Ok(true)
+    }
+
+    /// パーミッション 0600 で保存する (`passphrase` があれば暗号化する)
+    pub fn save(&self, passphrase: Option<&str>) -> Result<PathBuf, String> {
+        let file = match passphrase {
+            Some(passphrase) => CookieFile {
+                cookies: None,
+                encrypted: Some(encrypt(self, passphrase)?),
+            },
+            None => CookieFile {
+                cookies: Some(self.cookies.clone()),
+                encrypted: None,
+            },
+        };
+        let content = toml::to_string_pretty(&file).map_err(|e| e.to_string())?;
+
+        std::fs::create_dir_all(profile_dir()).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+        let path = Self::path();
+        write_private(&path, content.as_bytes())?;
+        Ok(path)
+    }
+}
+
+/// `atcoder.jp:443` -> `atcoder.jp`
+fn host_name(host: &str) -> &str {
+    host.split(':').next().unwrap_or(host)
+}
+
+#[cfg(unix)]
+fn write_private(path: &std::path::Path, content: &[u8]) -> Result<(), String> {
+    use std::{
+        io::Write,
+        os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|_e| ErrorMessages::FailedCreateFile)?;
+    // 以前から (0600 以外で) あったファイルにも適用する
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .map_err(|_e| ErrorMessages::FailedWrite)?;
+    file.write_all(content)
+        .map_err(|_e| ErrorMessages::FailedWrite.into())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &std::path::Path, content: &[u8]) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|_e| ErrorMessages::FailedWrite.into())
+}
+
+/// `ATCODER_COOKIE_PASSPHRASE` があればそれを、なければ端末で入力したものを使う
+pub fn read_passphrase(prompt: &str) -> Result<String, String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::read_password_from_tty(Some(prompt)).map_err(|e| e.to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key
+}
+
+fn encrypt(jar: &CookieJar, passphrase: &str) -> Result<EncryptedCookies, String> {
+    let salt: [u8; 16] = rand::random();
+    let nonce: [u8; 12] = rand::random();
+    let plain = toml::to_string(jar).map_err(|e| e.to_string())?;
+    let key = derive_key(passphrase, &salt);
+    let data = Aes256Gcm::new(&Key::from(key))
+        .encrypt(&Nonce::from(nonce), plain.as_bytes())
+        .map_err(|_e| "Failed to Encrypt Cookie")?;
+    Ok(EncryptedCookies {
+        salt: base64::encode(salt),
+        nonce: base64::encode(nonce),
+        data: base64::encode(data),
+    })
+}
+
+fn decrypt(encrypted: &EncryptedCookies, passphrase: &str) -> Result<CookieJar, String> {
+    let decode = |value: &str| base64::decode(value).map_err(|_e| "Broken Cookie File");
+    let salt = decode(&encrypted.salt)?;
+    let nonce = decode(&encrypted.nonce)?;
+    let data = decode(&encrypted.data)?;
+    let nonce = <[u8; 12]>::try_from(nonce.as_slice()).map_err(|_e| "Broken Cookie File")?;
+    let key = derive_key(passphrase, &salt);
+    let plain = Aes256Gcm::new(&Key::from(key))
+        .decrypt(&Nonce::from(nonce), data.as_ref())
+        .map_err(|_e| "Failed to Decrypt Cookie (wrong passphrase?)")?;
+    toml::from_str(&String::from_utf8_lossy(&plain)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_cookies_without_domain_matches_host_with_port() {
+        let response: reqwest::Response = http::Response::builder()
+            .header("set-cookie", "REVEL_SESSION=logged-in; Path=/")
+            .body("")
+            .unwrap()
+            .into();
+        let jar = CookieJar::from_cookies(response.cookies(), "127.0.0.1:8080");
+        assert_eq!(jar.cookies[0].domain, "127.0.0.1");
+        assert_eq!(
+            jar.header_value("127.0.0.1:8080").unwrap(),
+            "REVEL_SESSION=logged-in"
+        );
+    }
+}
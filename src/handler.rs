@@ -1,78 +1,353 @@
+mod heuristic;
+mod history;
+mod input_format;
+pub mod judge;
+mod library_checker;
+mod markdown;
+mod samples;
+mod score;
+mod session;
 mod templates;
 
+use futures::StreamExt;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
-use reqwest::{
-    header::{HeaderMap, HeaderValue, COOKIE},
-    Client,
-};
-use std::{
-    fs,
-    io::{BufRead, Write},
-    ops::Add,
-    process::Command,
-};
+use reqwest::header::{HeaderMap, COOKIE};
+use std::{fs, io::Write, ops::Add};
 
 use crate::{
-    handler::templates::{CHILD_FILE_TEMPLATE, TEST_FILE_CHILD_TEMPLATE, TEST_FILE_TEMPLATE},
+    config::config,
+    cookie_jar::CookieJar,
+    handler::{
+        heuristic::{copy_dir, extract_tools_zip, tools_aliases, IN_DIR, TOOLS_DIR},
+        history::{is_better, source_id, History, Run},
+        input_format::InputFormat,
+        judge::{judge_of_url, TaskListItem, TaskPage},
+        library_checker::{
+            case_ident, find_problem_dir, generate_cases, list_cases, ProblemInfo,
+            CHECKER_FILE_NAME,
+        },
+        markdown::Constraints,
+        samples::Samples,
+        score::{
+            build_runner, list_seeds, load_csv, print_summary, run_seeds, save_csv, seed_file,
+            SeedResult, Status, RESULTS_DIR,
+        },
+        session::{fetch_user_name, invalidate_session, parse_cookie_export},
+        templates::{
+            CHILD_FILE_TEMPLATE, CHILD_FILE_WITH_INPUT_TEMPLATE, TEST_FILE_CHILD_TEMPLATE,
+            TEST_FILE_TEMPLATE, TEST_FILE_WITH_CHECKER_CHILD_TEMPLATE,
+            TEST_FILE_WITH_CHECKER_TEMPLATE,
+        },
+    },
+    http::HttpClient,
+    metadata::{ContestMetadata, ProblemMetadata, METADATA_FILE_NAME},
+    parser::{AddTestInfo, LibraryCheckerInfo, LoginInfo, LoginMethod, ScoreInfo},
     utils::generate_options_file,
-    ContestInfo, ErrorMessages,
+    ContestInfo, Contests, ErrorMessages,
 };
 
 pub async fn create_contest_dir(contest_info: ContestInfo) {
     let name = contest_info.name.clone();
+    try_create_contest_dir(contest_info)
+        .await
+        .unwrap_or_else(|e| panic!("{}", e));
+    println!("Success to Create Contest Dir on `./{}`", name);
+}
+
+pub async fn batch_create_contest_dir(contest_infos: Vec<ContestInfo>, jobs: usize) {
+    let results = futures::stream::iter(contest_infos)
+        .map(|contest_info| async move {
+            let name = contest_info.name.clone();
+            let result = try_create_contest_dir(contest_info).await;
+            match &result {
+                Ok(()) => println!("Success to Create Contest Dir on `./{}`", name),
+                Err(e) => eprintln!("Failed to Create Contest Dir `./{}`: {}", name, e),
+            }
+            (name, result)
+        })
+        .buffer_unordered(jobs.max(1))
+        .collect::<Vec<(String, Result<(), String>)>>()
+        .await;
+
+    let (succeeded, failed): (Vec<_>, Vec<_>) =
+        results.into_iter().partition(|(_, result)| result.is_ok());
+    println!();
+    println!("Succeeded: {}", succeeded.len());
+    for (name, _) in succeeded.iter().sorted() {
+        println!("  {}", name);
+    }
+    println!("Failed: {}", failed.len());
+    for (name, result) in failed.iter().sorted_by(|a, b| a.0.cmp(&b.0)) {
+        println!("  {} ({})", name, result.as_ref().unwrap_err());
+    }
+}
+
+async fn try_create_contest_dir(contest_info: ContestInfo) -> Result<(), String> {
     if std::path::Path::new(&format!("./{}", &contest_info.name)).is_dir() {
-        panic!("Dir {} is Already Exists !", &contest_info.name)
-    }
-    Command::new("cargo")
-        .args(&[
-            "new",
-            "--bin",
-            &contest_info.name,
-            "--vcs",
-            "none",
-            "--edition",
-            "2018",
-        ])
+        return Err(format!("Dir {} is Already Exists !", &contest_info.name));
+    }
+    let problem_names = problem_names_of(contest_info.url.as_deref(), &contest_info.kind).await?;
+    create_cargo_dir(&contest_info.name, &problem_names).await?;
+
+    let problems = if let Contests::AHC = contest_info.kind {
+        if contest_info.wait {
+            wait_for_contest_start(contest_info.url.as_ref().unwrap()).await?;
+        }
+        generate_heuristic_dir(&contest_info)
+            .await
+            .map_err(|e| format!("Failed to Set up Heuristic Contest Dir: {}", e))?
+    } else if contest_info.wait {
+        wait_for_contest_start(contest_info.url.as_ref().unwrap()).await?;
+        generate_tests_dir_with_retry(&contest_info, &problem_names)
+            .await
+            .map_err(|e| format!("Failed to Generate Tests Dir: {}", e))?
+    } else if contest_info.url.is_some() {
+        generate_tests_dir(&contest_info, &problem_names, false)
+            .await
+            .map_err(|e| format!("Failed to Generate Tests Dir: {}", e))?
+    } else {
+        problem_names
+            .into_iter()
+            .map(ProblemMetadata::new)
+            .collect()
+    };
+    ContestMetadata {
+        contest_id: match &contest_info.url {
+            Some(url) => contest_id_of(url, Some(&contest_info.name)),
+            None => contest_info.name.clone(),
+        },
+        url: contest_info.url.clone(),
+        kind: contest_info.kind.typename().to_string(),
+        minimize: false,
+        problems,
+    }
+    .save(&contest_info.name)?;
+    Ok(())
+}
+
+/// `cargo new` して、問題ごとの `src/{x}.rs` と `Cargo.toml` の bin・エイリアスなどを作る
+async fn create_cargo_dir(name: &str, problem_names: &[String]) -> Result<(), String> {
+    let output = tokio::process::Command::new("cargo")
+        .args(["new", "--bin", name, "--vcs", "none", "--edition", "2018"])
         .output()
-        .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedCreateDir.value().to_string()));
-    fs::remove_file(format!("{}/src/main.rs", &contest_info.name))
-        .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedRemoveFile.value().to_string()));
+        .await
+        .map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    if !output.status.success() {
+        return Err(ErrorMessages::FailedCreateDir.into());
+    }
+    fs::remove_file(format!("{}/src/main.rs", name))
+        .map_err(|_e| ErrorMessages::FailedRemoveFile)?;
 
-    for x in contest_info.kind.problem_names() {
-        let mut child_file = fs::File::create(format!("{}/src/{}.rs", contest_info.name, x))
-            .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedCreateFile.value().to_string()));
+    for x in problem_names {
+        let mut child_file = fs::File::create(format!("{}/src/{}.rs", name, x))
+            .map_err(|_e| ErrorMessages::FailedCreateFile)?;
         child_file
             .write_all(CHILD_FILE_TEMPLATE.trim_start().as_bytes())
-            .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedWrite.value().to_string()))
+            .map_err(|_e| ErrorMessages::FailedWrite)?;
     }
 
-    generate_options_file(&contest_info.name, contest_info.kind.problem_names())
+    generate_options_file(name, problem_names.to_vec())
         .await
-        .expect("Error on `generate_options_file`");
-    if contest_info.url.is_some() {
-        generate_tests_dir(contest_info)
-            .await
-            .expect("Failed to Generate Tests Dir");
+        .map_err(|e| format!("Error on `generate_options_file`: {:?}", e))
+}
+
+/// コンテストのトップページから開始時刻を読み取り、開始時刻まで待つ
+async fn wait_for_contest_start(url: &str) -> Result<(), String> {
+    let client = create_cli_for(url);
+    let start_time = fetch_start_time(url, &client).await?;
+    println!("Contest starts at {}", start_time);
+    loop {
+        let remaining = start_time.signed_duration_since(chrono::Utc::now());
+        if remaining <= chrono::Duration::zero() {
+            break;
+        }
+        let seconds = remaining.num_seconds();
+        println!(
+            "Waiting for contest start ... {:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        // 残り時間が長い間は 1 分ごとに表示する
+        let sleep = remaining
+            .to_std()
+            .unwrap_or_default()
+            .min(std::time::Duration::from_secs(60));
+        tokio::time::delay_for(sleep).await;
     }
-    println!("Success to Create Contest Dir on `./{}`", name);
+    println!("Contest has started !");
+    Ok(())
+}
+
+static START_TIME_REGEX: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r#"var\s+startTime\s*=\s*moment\("([^"]+)"\)"#).unwrap());
+static FIXTIME_SELECTOR: Lazy<scraper::Selector> =
+    Lazy::new(|| scraper::Selector::parse(".contest-duration time.fixtime-full").unwrap());
+async fn fetch_start_time(
+    url: &str,
+    client: &HttpClient,
+) -> Result<chrono::DateTime<chrono::FixedOffset>, String> {
+    let html = client.get_text(url).await?;
+
+    if let Some(c) = START_TIME_REGEX.captures(&html) {
+        if let Ok(start_time) = chrono::DateTime::parse_from_rfc3339(&c[1]) {
+            return Ok(start_time);
+        }
+    }
+    let doc = scraper::Html::parse_document(&html);
+    doc.select(&FIXTIME_SELECTOR)
+        .next()
+        .and_then(|time| {
+            chrono::DateTime::parse_from_str(
+                time.text().collect::<String>().trim(),
+                "%Y-%m-%d %H:%M:%S%z",
+            )
+            .ok()
+        })
+        .ok_or_else(|| "Failed to Find Start Time of Contest".to_string())
+}
+
+/// 開始直後は問題ページが見られないことがあるので、しばらくの間 backoff しつつ再試行する
+async fn generate_tests_dir_with_retry(
+    contest_info: &ContestInfo,
+    problem_names: &[String],
+) -> Result<Vec<ProblemMetadata>, String> {
+    const MAX_RETRY: u32 = 6;
+    let tests_dir = format!("{}/tests", contest_info.name);
+    let mut delay = std::time::Duration::from_secs(1);
+    let mut retry = 0;
+    loop {
+        // 開始直後は問題ページがまだ見られないことがあるので、取得できない問題があれば作り直す
+        match generate_tests_dir(contest_info, problem_names, true).await {
+            Ok(problems) => return Ok(problems),
+            Err(e) if retry < MAX_RETRY => {
+                println!("Failed to Fetch Tests ({}), retry in {:?}", e, delay);
+                if std::path::Path::new(&tests_dir).is_dir() {
+                    fs::remove_dir_all(&tests_dir).map_err(|_e| ErrorMessages::FailedRemoveDir)?;
+                }
+                tokio::time::delay_for(delay).await;
+                delay *= 2;
+                retry += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// AHC 用に `tools/`, `in/` と `cargo gen` などのエイリアスを用意する
+///
+/// サンプルのテストは作らず、問題文と解答ファイルのヘッダーだけを問題ページから作る
+async fn generate_heuristic_dir(
+    contest_info: &ContestInfo,
+) -> Result<Vec<ProblemMetadata>, String> {
+    let dir = std::path::Path::new(&contest_info.name);
+    let tools_dir = dir.join(TOOLS_DIR);
+
+    let mut problem = ProblemMetadata::new("a");
+    let mut tools_url = None;
+    if let Some(url) = &contest_info.url {
+        let client = create_cli_for(url);
+        let task = fetch_task_list(url, &client)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or("Problem is not Found in Tasks Page")?;
+        problem.title = Some(task.title);
+        problem.url = Some(task.url);
+        problem.time_limit_ms = task.time_limit_ms;
+        problem.memory_limit_mb = task.memory_limit_mb;
+        let page = fetch_task_page(problem.url.as_ref().unwrap(), &client).await?;
+        write_task_page_files(&format!("{}/tests", contest_info.name), &problem, &page)?;
+        tools_url = page.tools_url;
+    }
+
+    match (&contest_info.tools, tools_url) {
+        (Some(path), _) if std::path::Path::new(path).is_dir() => {
+            copy_dir(std::path::Path::new(path), &tools_dir)?
+        }
+        (Some(path), _) => {
+            let bytes = fs::read(path).map_err(|e| format!("Failed to Read {}: {}", path, e))?;
+            extract_tools_zip(&bytes, &tools_dir)?
+        }
+        (None, Some(url)) => {
+            // 問題文のリンク先は AtCoder とは限らない
+            let bytes = create_cli_for(&url)
+                .get(&url)
+                .await?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .bytes()
+                .await
+                .map_err(|e| e.to_string())?;
+            extract_tools_zip(&bytes, &tools_dir)?
+        }
+        (None, None) => eprintln!(
+            "Local Tester is not Found (put it on `{}/{}` or use `--tools <PATH>`)",
+            contest_info.name, TOOLS_DIR
+        ),
+    }
+
+    // 配布された入力があればそれを使う (なければ `cargo gen` で作る)
+    let in_dir = dir.join(IN_DIR);
+    if tools_dir.join(IN_DIR).is_dir() {
+        copy_dir(&tools_dir.join(IN_DIR), &in_dir)?;
+    } else {
+        fs::create_dir_all(&in_dir).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    }
+
+    fs::OpenOptions::new()
+        .append(true)
+        .open(dir.join(".cargo/config.toml"))
+        .and_then(|mut file| file.write_all(tools_aliases(&tools_dir).as_bytes()))
+        .map_err(|_e| ErrorMessages::FailedWrite)?;
+    Ok(vec![problem])
+}
+
+pub async fn login(login_info: LoginInfo) {
+    let LoginInfo { method, passphrase } = login_info;
+    let failure = match method {
+        LoginMethod::Password(..) => "Failed to Login (wrong user name or password?)",
+        LoginMethod::CookieFile(_) => "Cookie is not Valid (expired or not logged in)",
+    };
+    let jar = match method {
+        LoginMethod::Password(user_name, password) => {
+            login_with_password(user_name, password).await
+        }
+        LoginMethod::CookieFile(path) => read_cookie_file(path),
+    };
+
+    // パスワードが違う・CAPTCHA が出たなどの場合もログインページが返ってくるだけなので、
+    // 保存する前にログイン中のユーザー名が読めるか確かめる
+    let mut session_headers = HeaderMap::new();
+    if let Some(value) = jar.header_value(config().base_host()) {
+        session_headers.insert(COOKIE, value);
+    }
+    let logged_in_as = fetch_user_name(&HttpClient::new(session_headers))
+        .await
+        .unwrap_or_else(|e| panic!("Failed to Check Login: {}", e))
+        .unwrap_or_else(|| panic!("{}", failure));
+
+    let cookie_path = jar
+        .save(passphrase.as_deref())
+        .unwrap_or_else(|e| panic!("{}", e));
+    println!("Logged in as {}{}", logged_in_as, profile_suffix());
+    println!("Saved Your cookie in \"{}\"", cookie_path.to_str().unwrap());
 }
 
-pub async fn login(user_name: String, password: String) {
-    let client = create_cli();
-    let login_url = "https://atcoder.jp/login";
+async fn login_with_password(user_name: String, password: String) -> CookieJar {
+    let client = HttpClient::new(HeaderMap::new());
+    let login_url = &format!("{}/login", config().base_url);
     let resp = client
         .get(login_url)
-        .send()
         .await
         .expect("Failed to Get Login Page");
     let mut cookie_headers = HeaderMap::new();
-    resp.cookies().for_each(|cookie| {
-        cookie_headers.insert(
-            COOKIE,
-            HeaderValue::from_str(&format!("{}={}", cookie.name(), cookie.value())).unwrap(),
-        );
-    });
+    if let Some(value) = CookieJar::from_cookies(resp.cookies(), config().base_host())
+        .header_value(config().base_host())
+    {
+        cookie_headers.insert(COOKIE, value);
+    }
     let html = resp.text().await.expect("Failed to Get Login Page");
     let document = scraper::Html::parse_document(&html);
 
@@ -96,59 +371,544 @@ pub async fn login(user_name: String, password: String) {
     .collect();
 
     let resp = client
-        .post(login_url)
-        .headers(cookie_headers)
-        .form(&params)
-        .send()
+        .post_form(login_url, cookie_headers, &params)
         .await
         .expect("Failed to Post Login");
+    CookieJar::from_cookies(resp.cookies(), config().base_host())
+}
 
-    let cookies_str = resp
-        .cookies()
-        .map(|cookie| format!("{}={}", cookie.name(), cookie.value()))
-        .join(";");
-    let path = dirs::home_dir()
-        .unwrap()
-        .join(".atcoder-create-contest-dir");
-    std::fs::create_dir_all(path.clone())
-        .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedCreateDir.value().to_string()));
-    let cookie_path = path.join("cookie");
-    std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(cookie_path.clone())
-        .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedCreateFile.value().to_string()))
-        .write_all(cookies_str.as_bytes())
-        .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedWrite.value().to_string()));
-    println!("Saved Your cookie in \"{}\"", cookie_path.to_str().unwrap());
+/// ブラウザから書き出した cookie を読み込む (パスワードでのログインが弾かれる場合用)
+///
+/// `path` が `-` のときは標準入力から読み込む
+fn read_cookie_file(path: String) -> CookieJar {
+    let content = if path == "-" {
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+            .unwrap_or_else(|e| panic!("Failed to Read stdin: {}", e));
+        content
+    } else {
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to Read {}: {}", path, e))
+    };
+    let cookie =
+        parse_cookie_export(&content, config().base_host()).unwrap_or_else(|e| panic!("{}", e));
+    CookieJar {
+        cookies: vec![cookie],
+    }
+}
+
+/// 保存した cookie のセッションでログインできているか (コンテストの前に切れていないか) を確かめる
+pub async fn whoami() {
+    let jar = match CookieJar::load(config().base_host()).unwrap_or_else(|e| panic!("{}", e)) {
+        Some(jar) => jar,
+        None => {
+            eprintln!("Not Logged in (run `{}`)", login_command());
+            std::process::exit(1);
+        }
+    };
+    let expiry = jar.session_expiry();
+
+    // 読み込んだ jar をそのまま使う (`create_cli` だとパスフレーズをもう一度聞いてしまう)
+    let client = HttpClient::new(cookie_headers(&jar).unwrap_or_default());
+    match fetch_user_name(&client)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to Check Session: {}", e))
+    {
+        Some(user_name) => {
+            println!("Logged in as {}{}", user_name, profile_suffix());
+            if let Some(expiry) = expiry {
+                println!(
+                    "Session expires at {}",
+                    expiry
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S %:z")
+                );
+            }
+        }
+        None => {
+            match expiry.filter(|expiry| *expiry <= chrono::Utc::now()) {
+                Some(expiry) => eprintln!(
+                    "Session has Expired at {}",
+                    expiry
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S %:z")
+                ),
+                None => eprintln!("Session is not Valid"),
+            }
+            eprintln!("Run `{}` again", login_command());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// サーバー側でセッションを無効にしてから、保存した cookie を削除する
+///
+/// サーバーに届かなかった場合も cookie は削除する
+pub async fn logout() {
+    let jar = match CookieJar::load(config().base_host()).unwrap_or_else(|e| panic!("{}", e)) {
+        Some(jar) => jar,
+        None => {
+            println!("Not Logged in{}", profile_suffix());
+            return;
+        }
+    };
+    // 読み込んだ jar をそのまま使う (`create_cli` だとパスフレーズをもう一度聞いてしまう)
+    let client = HttpClient::new(cookie_headers(&jar).unwrap_or_default());
+    if let Err(e) = invalidate_session(&client).await {
+        eprintln!("Failed to Invalidate the Session on the Server: {}", e);
+    }
+    CookieJar::remove().unwrap_or_else(|e| panic!("{}", e));
+    println!(
+        "Removed Your cookie in \"{}\"{}",
+        CookieJar::path().to_str().unwrap(),
+        profile_suffix()
+    );
+}
+
+/// ` (profile `NAME`)` (プロファイルを指定していなければ空)
+fn profile_suffix() -> String {
+    match &config().profile {
+        Some(profile) => format!(" (profile `{}`)", profile),
+        None => String::new(),
+    }
+}
+
+fn login_command() -> String {
+    match &config().profile {
+        Some(profile) => format!("create-contest --profile {} login", profile),
+        None => "create-contest login".to_string(),
+    }
 }
 
-pub async fn add_test(url: String, problem_names: Vec<String>) {
+pub async fn add_test(add_test_info: AddTestInfo) {
+    let AddTestInfo { url, kind, problem } = add_test_info;
     if !std::path::Path::new("Cargo.toml").is_file() {
         panic!("Missing Cargo.toml on This Dir")
     }
+    let metadata = ContestMetadata::load(".").unwrap_or_else(|e| panic!("{}", e));
+    let url = url
+        .or_else(|| metadata.as_ref().and_then(|m| m.url.clone()))
+        .unwrap_or_else(|| panic!("URL is Required (not found in `{}`)", METADATA_FILE_NAME));
+    let kind = kind
+        .or_else(|| {
+            metadata
+                .as_ref()
+                .and_then(|m| Contests::from_typename(m.kind.as_str()))
+        })
+        .unwrap_or_else(|| panic!("Type is Required (not found in `{}`)", METADATA_FILE_NAME));
+
+    if let Some(problem) = problem {
+        let problem_metadata = update_problem_tests("tests", &url, &kind, &problem, &metadata)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to Update Tests of `{}`: {}", problem, e));
+        let mut metadata = metadata.unwrap_or_else(|| ContestMetadata {
+            contest_id: contest_id_of(&url, None),
+            url: Some(url.clone()),
+            kind: kind.typename().to_string(),
+            minimize: false,
+            problems: kind
+                .problem_names()
+                .into_iter()
+                .map(ProblemMetadata::new)
+                .collect(),
+        });
+        match metadata.problem_mut(&problem) {
+            Some(p) => *p = problem_metadata,
+            None => metadata.problems.push(problem_metadata),
+        }
+        metadata.save(".").unwrap_or_else(|e| panic!("{}", e));
+        return;
+    }
+
     if std::path::Path::new("tests").is_dir() {
         fs::remove_dir_all("tests")
             .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedRemoveDir.value().to_string()));
     }
     fs::create_dir("tests")
         .unwrap_or_else(|_| panic!("{}", ErrorMessages::FailedCreateDir.value().to_string()));
-    generate_tests_files("tests", url, problem_names)
+    let problem_names = problem_names_of(Some(&url), &kind)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to Fetch Tasks: {}", e));
+    let problems = generate_tests_files("tests", url.clone(), problem_names, false)
         .await
         .expect("Failed on `generate_tests_files`");
+    ContestMetadata {
+        contest_id: contest_id_of(&url, metadata.as_ref().map(|m| m.contest_id.as_str())),
+        url: Some(url),
+        kind: kind.typename().to_string(),
+        minimize: metadata.as_ref().is_some_and(|m| m.minimize),
+        problems,
+    }
+    .save(".")
+    .unwrap_or_else(|e| panic!("{}", e));
 }
 
-async fn generate_tests_dir(contest_info: ContestInfo) -> Result<(), String> {
+/// ローカルの library-checker-problems の問題から、リポジトリの checker で判定するテストのディレクトリを作る
+///
+/// 入力・出力と checker はリポジトリの `generate.py` で作り、ネットワークには接続しない
+pub async fn library_checker(info: LibraryCheckerInfo) {
+    try_create_library_checker_dir(&info)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to Create `{}`: {}", info.name, e));
+}
+
+async fn try_create_library_checker_dir(info: &LibraryCheckerInfo) -> Result<(), String> {
+    let dir_name = format!("lc-{}", info.name);
+    if std::path::Path::new(&dir_name).is_dir() {
+        return Err(format!("Dir {} is Already Exists !", dir_name));
+    }
+    let repo = fs::canonicalize(&info.repo)
+        .map_err(|_e| format!("Repository `{}` is not Found", info.repo))?;
+    let problem_dir = find_problem_dir(&repo, &info.name)?;
+    generate_cases(&repo, &info.name).await?;
+    if !problem_dir.join(CHECKER_FILE_NAME).is_file() {
+        return Err(format!("Checker is not Found in {}", problem_dir.display()));
+    }
+    let cases = list_cases(&problem_dir)?;
+    if cases.is_empty() {
+        return Err(format!("Cases are not Found in {}", problem_dir.display()));
+    }
+    let problem_info = ProblemInfo::load(&problem_dir)?;
+
+    let problem_names = Contests::LC.problem_names();
+    create_cargo_dir(&dir_name, &problem_names).await?;
+
+    let tests_path = format!("{}/tests", dir_name);
+    fs::create_dir(&tests_path).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    let problem = ProblemMetadata {
+        label: problem_names[0].clone(),
+        title: problem_info.title.clone(),
+        url: None,
+        time_limit_ms: problem_info.time_limit_ms(),
+        memory_limit_mb: None,
+        sample_count: cases.len(),
+    };
+    write_checker_test_file(&tests_path, &problem.label, &problem_dir, &cases)?;
+    if let Ok(task) = fs::read_to_string(problem_dir.join("task.md")) {
+        write_statement_file(&tests_path, &problem, &task)?;
+    }
+    write_solution_file(&tests_path, &problem, None, None)?;
+
+    ContestMetadata {
+        contest_id: info.name.clone(),
+        url: None,
+        kind: Contests::LC.typename().to_string(),
+        minimize: false,
+        problems: vec![problem],
+    }
+    .save(&dir_name)?;
+    println!("{} cases of `{}` are added", cases.len(), info.name);
+    Ok(())
+}
+
+/// AHC のディレクトリで、各シードの入力に対して解答を実行し、ローカルテスターでスコアを計算する
+pub async fn score(score_info: ScoreInfo) {
+    /// 問題の実行時間制限がわからない場合の上限
+    const DEFAULT_TIME_LIMIT: std::time::Duration = std::time::Duration::from_secs(10);
+
+    let ScoreInfo {
+        seeds,
+        jobs,
+        time_limit,
+    } = score_info;
+    let (metadata, problem) = load_heuristic_metadata();
+    let time_limit = time_limit
+        .or_else(|| problem.time_limit_ms.map(std::time::Duration::from_millis))
+        .unwrap_or(DEFAULT_TIME_LIMIT);
+
+    let seeds = seeds.unwrap_or_else(list_seeds);
+    if seeds.is_empty() {
+        panic!("No Input is Found on `{}` (run `cargo gen`)", IN_DIR);
+    }
+    if let Some(seed) = seeds
+        .iter()
+        .find(|&&seed| !seed_file(IN_DIR, seed).is_file())
+    {
+        panic!(
+            "Input of Seed {} is not Found on `{}` (run `cargo gen`)",
+            seed, IN_DIR
+        );
+    }
+
+    // 実行中に書き換えられてもよいように、ビルドする前のものを記録する
+    let source = source_id(&solution_path(&problem)).unwrap_or_else(|e| panic!("{}", e));
+    let runner = build_runner(&problem.label, time_limit)
+        .await
+        .unwrap_or_else(|e| panic!("{}", e));
+    let results = run_seeds(&runner, &seeds, jobs)
+        .await
+        .unwrap_or_else(|e| panic!("{}", e));
+    print_summary(&results);
+    let path = save_csv(&results).unwrap_or_else(|e| panic!("{}", e));
+    println!("Saved Results to `{}`", path.display());
+
+    let mut history = History::load().unwrap_or_else(|e| panic!("{}", e));
+    let run = Run {
+        file: path.file_name().unwrap().to_string_lossy().to_string(),
+        source,
+        total: results.iter().map(|r| r.score.unwrap_or(0)).sum(),
+    };
+    let new_bests = history.record(run, &results, metadata.minimize);
+    history.save().unwrap_or_else(|e| panic!("{}", e));
+    if !new_bests.is_empty() {
+        println!("New Best: {} seeds", new_bests.len());
+        for best in new_bests {
+            match best.old {
+                Some(old) => println!("  {:04}: {} -> {}", best.seed, old, best.new),
+                None => println!("  {:04}: {}", best.seed, best.new),
+            }
+        }
+    }
+}
+
+/// AHC のディレクトリで、最後に `score` を実行した結果をそれまでの最高スコア (`previous` なら前回の結果) と比べる
+pub async fn compare(previous: bool) {
+    let (metadata, problem) = load_heuristic_metadata();
+    let history = History::load().unwrap_or_else(|e| panic!("{}", e));
+    let latest = history
+        .runs
+        .last()
+        .unwrap_or_else(|| panic!("No Result is Found (run `create-contest score`)"));
+    let load = |run: &Run| {
+        load_csv(&std::path::Path::new(RESULTS_DIR).join(&run.file))
+            .unwrap_or_else(|e| panic!("{}", e))
+    };
+    let current = load(latest);
+    if source_id(&solution_path(&problem)).ok().as_ref() != Some(&latest.source) {
+        eprintln!(
+            "Warning: `src/{}.rs` has Changed since the Last Run (run `create-contest score` to update)",
+            problem.label
+        );
+    }
+
+    let (name, baseline) = if previous {
+        let run = history
+            .runs
+            .iter()
+            .rev()
+            .nth(1)
+            .unwrap_or_else(|| panic!("Previous Run is not Found"));
+        let scores = load(run)
+            .iter()
+            .map(|r| (r.seed, r.score.unwrap_or(0)))
+            .collect();
+        (format!("{} ({})", run.file, run.source), scores)
+    } else {
+        // 最後の実行はすでに `best` に反映されているので、その前の最高スコアと比べる
+        let scores = history
+            .previous_best
+            .iter()
+            .map(|b| (b.seed, b.score))
+            .collect();
+        ("best".to_string(), scores)
+    };
+    println!(
+        "Comparing {} ({}) with {}",
+        latest.file, latest.source, name
+    );
+    print_comparison(&current, &baseline, metadata.minimize);
+}
+
+/// シードごとの改善・悪化と、両方にあるシードの合計を表示する (失敗したシードは 0 点)
+fn print_comparison(
+    current: &[SeedResult],
+    baseline: &std::collections::BTreeMap<u32, i64>,
+    minimize: bool,
+) {
+    let (mut improved, mut regressed, mut unchanged) = (0, 0, 0);
+    let (mut total, mut baseline_total) = (0, 0);
+    for result in current {
+        let score = result.score.unwrap_or(0);
+        let status = match result.status {
+            Status::Ok => String::new(),
+            status => format!(" {}", status.value()),
+        };
+        let old = match baseline.get(&result.seed) {
+            Some(&old) => old,
+            None => {
+                println!("{:04}: - -> {}{} (new)", result.seed, score, status);
+                continue;
+            }
+        };
+        total += score;
+        baseline_total += old;
+        let label = if is_better(score, old, minimize) {
+            improved += 1;
+            "improved"
+        } else if is_better(old, score, minimize) {
+            regressed += 1;
+            "regressed"
+        } else {
+            unchanged += 1;
+            continue;
+        };
+        let ratio = match old {
+            0 => String::new(),
+            old => format!(", {:+.2}%", (score - old) as f64 / old.abs() as f64 * 100.0),
+        };
+        println!(
+            "{:04}: {} -> {}{} ({:+}{}) {}",
+            result.seed,
+            old,
+            score,
+            status,
+            score - old,
+            ratio,
+            label
+        );
+    }
+    println!();
+    println!(
+        "Improved: {}, Regressed: {}, Unchanged: {}",
+        improved, regressed, unchanged
+    );
+    println!(
+        "Total: {} -> {} ({:+})",
+        baseline_total,
+        total,
+        total - baseline_total
+    );
+}
+
+/// AHC のディレクトリの `contest.toml` と、その問題
+fn load_heuristic_metadata() -> (ContestMetadata, ProblemMetadata) {
+    let metadata = ContestMetadata::load(".")
+        .unwrap_or_else(|e| panic!("{}", e))
+        .filter(|m| m.kind == Contests::AHC.typename())
+        .unwrap_or_else(|| {
+            panic!(
+                "This is not AHC Dir (`kind = 'ahc'` is not found in `{}`)",
+                METADATA_FILE_NAME
+            )
+        });
+    let problem = metadata
+        .problems
+        .first()
+        .cloned()
+        .unwrap_or_else(|| ProblemMetadata::new("a"));
+    (metadata, problem)
+}
+
+fn solution_path(problem: &ProblemMetadata) -> std::path::PathBuf {
+    std::path::Path::new("src").join(format!("{}.rs", problem.label))
+}
+
+/// `tests/{problem}/` と `tests/{problem}.rs` だけを作り直し、変更のあったサンプルを表示する
+async fn update_problem_tests(
+    path: &str,
+    url: &str,
+    kind: &Contests,
+    problem: &str,
+    metadata: &Option<ContestMetadata>,
+) -> Result<ProblemMetadata, String> {
+    let client = create_cli_for(url);
+
+    let mut problem_metadata = match metadata.as_ref().and_then(|m| m.problem(problem)) {
+        Some(p) if p.url.is_some() => p.clone(),
+        _ => {
+            let mut tasks = fetch_task_list(url, &client).await?;
+            // label が一致する問題、なければ問題の順番で探す
+            let pos = tasks
+                .iter()
+                .position(|task| task.label.eq_ignore_ascii_case(problem))
+                .or_else(|| kind.problem_names().iter().position(|x| x == problem))
+                .ok_or(format!("Unknown Problem `{}`", problem))?;
+            if pos >= tasks.len() {
+                return Err(format!("Problem `{}` is not Found in Tasks Page", problem));
+            }
+            let task = tasks.swap_remove(pos);
+            ProblemMetadata {
+                label: problem.to_string(),
+                title: Some(task.title),
+                url: Some(task.url),
+                time_limit_ms: task.time_limit_ms,
+                memory_limit_mb: task.memory_limit_mb,
+                sample_count: 0,
+            }
+        }
+    };
+
+    let dir = format!("{}/{}", path, problem);
+    let old_samples = read_sample_files(&format!("{}/{}", dir, problem));
+    let page = fetch_task_page(problem_metadata.url.as_ref().unwrap(), &client).await?;
+    report_sample_warnings(problem, &page.samples);
+    let samples = &page.samples.samples;
+
+    if std::path::Path::new(&dir).is_dir() {
+        fs::remove_dir_all(&dir).map_err(|_e| ErrorMessages::FailedRemoveDir)?;
+    }
+    fs::create_dir_all(&dir).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    write_sample_files(&format!("{}/{}", dir, problem), samples)
+        .map_err(|_e| "Failed to Create Sample Files")?;
+    write_test_file(path, problem, samples.len())?;
+
+    for i in 0..old_samples.len().max(samples.len()) {
+        let status = match (old_samples.get(i), samples.get(i)) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (Some(old), Some(new)) if old != new => "changed",
+            _ => continue,
+        };
+        println!("sample {}_{}: {}", problem, i + 1, status);
+    }
+    if &old_samples == samples {
+        println!("samples of `{}` are not changed", problem);
+    }
+
+    problem_metadata.sample_count = samples.len();
+    problem_metadata.time_limit_ms = problem_metadata.time_limit_ms.or(page.time_limit_ms);
+    problem_metadata.memory_limit_mb = problem_metadata.memory_limit_mb.or(page.memory_limit_mb);
+    write_task_page_files(path, &problem_metadata, &page)?;
+    Ok(problem_metadata)
+}
+
+/// 既存のサンプルファイル (`{path}_1.input`, `{path}_1.output`, ...) を読み込む
+fn read_sample_files(path: &str) -> Vec<(String, String)> {
+    (1..)
+        .map(|i| {
+            let input = fs::read_to_string(format!("{}_{}.input", path, i)).ok()?;
+            let output = fs::read_to_string(format!("{}_{}.output", path, i)).ok()?;
+            Some((input, output))
+        })
+        .take_while(|sample| sample.is_some())
+        .flatten()
+        .collect()
+}
+
+fn contest_id_of(url: &str, default: Option<&str>) -> String {
+    judge_of_url(url)
+        .map(|(_judge, contest_id)| contest_id)
+        .or_else(|| default.map(|x| x.to_string()))
+        .unwrap_or_default()
+}
+
+/// 作成する問題の名前 (問題の一覧から決めるジャッジでは、一覧の label を小文字にしたもの)
+async fn problem_names_of(url: Option<&str>, kind: &Contests) -> Result<Vec<String>, String> {
+    match url.and_then(|url| judge_of_url(url).map(|(judge, _)| (url, judge))) {
+        Some((url, judge)) if judge.labels_from_task_list() => {
+            let tasks = fetch_task_list(url, &create_cli_for(url)).await?;
+            Ok(tasks
+                .into_iter()
+                .map(|task| task.label.to_lowercase())
+                .collect())
+        }
+        _ => Ok(kind.problem_names()),
+    }
+}
+
+async fn generate_tests_dir(
+    contest_info: &ContestInfo,
+    problem_names: &[String],
+    fail_on_missing: bool,
+) -> Result<Vec<ProblemMetadata>, String> {
     fs::create_dir(format!("{}/tests", contest_info.name))
         .map_err(|_e| ErrorMessages::FailedCreateDir)?;
     generate_tests_files(
         format!("{}/tests", contest_info.name),
-        contest_info.url.unwrap(),
-        contest_info.kind.problem_names(),
+        contest_info.url.clone().unwrap(),
+        problem_names.to_vec(),
+        fail_on_missing,
     )
-    .await?;
-    Ok(())
+    .await
 }
 
 /**
@@ -157,136 +917,298 @@ example:
    generate_tests_files(
        /*     path: */ "abc-000/tests",
        /* base_url: */ "https://atcoder.jp/contests/abc000",
-       /*     kind: */ Contests::ABC
+       /*     kind: */ Contests::ABC,
+       /*  fail_on_missing: */ false,
    )
  ```
+
+`fail_on_missing` なら、問題ページを取得できない問題があるときにテストを書き込まずに Err を返す
+(そうでなければその問題のサンプルは空にして続ける)
 */
 async fn generate_tests_files(
     path: impl Into<String>,
     base_url: impl Into<String>,
     problem_names: Vec<String>,
-) -> Result<(), String> {
-    let cookie_headers = get_local_cookie_header().unwrap_or_default();
+    fail_on_missing: bool,
+) -> Result<Vec<ProblemMetadata>, String> {
     let path: String = path.into();
     let url: String = base_url.into();
 
-    let client = create_cli();
-    let problem_urls =
-        fetch_sample_urls(&format!("{}/tasks", url), &cookie_headers, &client).await?;
-    for (idx, url) in problem_names.into_iter().zip(problem_urls) {
+    let client = create_cli_for(&url);
+    let tasks = fetch_task_list(&url, &client).await?;
+
+    // 問題ページは並列に取得し、書き込みは問題の順に行う
+    let fetched = futures::stream::iter(problem_names.into_iter().zip(tasks))
+        .map(|(idx, task)| {
+            let client = &client;
+            async move {
+                let page = fetch_task_page(&task.url, client).await;
+                (idx, task, page)
+            }
+        })
+        .buffered(config().fetch_jobs.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    if fail_on_missing {
+        let missing = fetched
+            .iter()
+            .filter_map(|(idx, _task, page)| Some((idx, page.as_ref().err()?)))
+            .collect::<Vec<_>>();
+        for (idx, e) in &missing {
+            eprintln!("Failed to Fetch Samples of `{}`: {}", idx, e);
+        }
+        if !missing.is_empty() {
+            return Err(format!(
+                "Samples of {} are not Fetched",
+                missing.iter().map(|(idx, _e)| format!("`{}`", idx)).join(", ")
+            ));
+        }
+    }
+
+    let mut problems = Vec::new();
+    let mut failed = Vec::new();
+    for (idx, task, page) in fetched {
         fs::create_dir(format!("{}/{}", &path, idx))
             .map_err(|_e| ErrorMessages::FailedCreateDir)?;
-        let sample_cnt = generate_sample_test_file(
-            url.as_str(),
-            &format!("{}/{}/{}", &path, idx, idx),
-            &cookie_headers,
-            &client,
-        )
-        .await
-        .map_err(|_e| "Failed to Create Sample Files")?;
-        let mut test_file = fs::OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(format!("{}/{}.rs", &path, idx))
-            .map_err(|_e| ErrorMessages::FailedCreateFile)?;
-        test_file
-            .write_all(
-                TEST_FILE_TEMPLATE
-                    .to_string()
-                    .add(
-                        (0..sample_cnt)
-                            .map(|i| {
-                                TEST_FILE_CHILD_TEMPLATE.replace("{{num}}", &(i + 1).to_string())
-                            })
-                            .join("\n")
-                            .as_str(),
-                    )
-                    .replace("{{name}}", &idx)
-                    .as_bytes(),
-            )
-            .map_err(|_e| ErrorMessages::FailedWrite)?;
+        let page = match page {
+            Ok(page) => {
+                report_sample_warnings(&idx, &page.samples);
+                Some(page)
+            }
+            Err(e) => {
+                eprintln!("Failed to Fetch Samples of `{}`: {}", idx, e);
+                failed.push(idx.clone());
+                None
+            }
+        };
+        let samples = page.as_ref().map_or(&[][..], |page| &page.samples.samples);
+        write_sample_files(&format!("{}/{}/{}", &path, idx, idx), samples)
+            .map_err(|_e| "Failed to Create Sample Files")?;
+        write_test_file(&path, &idx, samples.len())?;
+        let problem = ProblemMetadata {
+            label: idx,
+            title: Some(task.title),
+            url: Some(task.url),
+            time_limit_ms: task
+                .time_limit_ms
+                .or_else(|| page.as_ref().and_then(|page| page.time_limit_ms)),
+            memory_limit_mb: task
+                .memory_limit_mb
+                .or_else(|| page.as_ref().and_then(|page| page.memory_limit_mb)),
+            sample_count: samples.len(),
+        };
+        if let Some(page) = &page {
+            write_task_page_files(&path, &problem, page)?;
+        }
+        problems.push(problem);
+    }
+    if !failed.is_empty() {
+        eprintln!(
+            "Failed to Fetch Samples of {} (retry with `create-contest add_test --problem <PROBLEM>`)",
+            failed.iter().map(|x| format!("`{}`", x)).join(", ")
+        );
     }
 
-    Ok(())
+    Ok(problems)
 }
 
-static TABLE_SELECTOR: Lazy<scraper::Selector> =
-    Lazy::new(|| scraper::Selector::parse("table").unwrap());
-static TH_SELECTOR: Lazy<scraper::Selector> =
-    Lazy::new(|| scraper::Selector::parse("thead th").unwrap());
-static TR_SELECTOR: Lazy<scraper::Selector> =
-    Lazy::new(|| scraper::Selector::parse("tbody tr").unwrap());
-static TD_SELECTOR: Lazy<scraper::Selector> = Lazy::new(|| scraper::Selector::parse("td").unwrap());
-static A_SELECTOR: Lazy<scraper::Selector> = Lazy::new(|| scraper::Selector::parse("a").unwrap());
-async fn fetch_sample_urls(
-    tasks_url: &str,
-    cookie_headers: &HeaderMap,
-    client: &Client,
-) -> Result<Vec<String>, String> {
-    let html = client
-        .get(tasks_url)
-        .headers(cookie_headers.clone())
-        .send()
-        .await
-        .map_err(|_e| _e.to_string())?
-        .text()
-        .await
-        .map_err(|_e| _e.to_string())?;
-    let doc = scraper::Html::parse_document(&html);
+/// 問題ページから作る、サンプル以外のファイル (問題文・解答のテンプレート) を書き込む
+fn write_task_page_files(
+    tests_path: &str,
+    problem: &ProblemMetadata,
+    page: &TaskPage,
+) -> Result<(), String> {
+    if let Some(statement) = &page.statement {
+        write_statement_file(tests_path, problem, statement)?;
+    }
+    write_solution_file(
+        tests_path,
+        problem,
+        page.constraints.as_ref(),
+        page.input.as_ref(),
+    )
+}
 
-    for table in doc.select(&TABLE_SELECTOR) {
-        let pos = match table
-            .select(&TH_SELECTOR)
-            .position(|element| match element.text().next() {
-                Some(text) => text == "問題名" || text == "Task Name",
-                None => false,
-            }) {
-            Some(p) => p,
-            None => continue,
-        };
+/// `tests` と同じ階層の `statements/{label}.md` に問題文を書き込む
+fn write_statement_file(
+    tests_path: &str,
+    problem: &ProblemMetadata,
+    statement: &str,
+) -> Result<(), String> {
+    let dir = std::path::Path::new(tests_path)
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new(""))
+        .join("statements");
+    fs::create_dir_all(&dir).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+
+    let mut content = format!(
+        "# {} - {}\n\n",
+        problem.label.to_uppercase(),
+        problem.title.as_deref().unwrap_or_default()
+    );
+    if let Some(url) = &problem.url {
+        content += &format!("<{}>\n\n", url);
+    }
+    if let (Some(time_limit), Some(memory_limit)) = (problem.time_limit_ms, problem.memory_limit_mb)
+    {
+        content += &format!(
+            "Time Limit: {} ms / Memory Limit: {} MB\n\n",
+            time_limit, memory_limit
+        );
+    }
+    content += statement;
+    fs::write(dir.join(format!("{}.md", problem.label)), content)
+        .map_err(|_e| ErrorMessages::FailedWrite.into())
+}
 
-        let res = table
-            .select(&TR_SELECTOR)
-            .map(|tr_element| {
-                let td_elements = tr_element
-                    .select(&TD_SELECTOR)
-                    .collect::<Vec<scraper::ElementRef>>();
-                let link = td_elements[pos]
-                    .select(&A_SELECTOR)
-                    .next()
-                    .unwrap()
-                    .value()
-                    .attr("href")
-                    .unwrap();
-                "https://atcoder.jp".to_string() + link
-            })
-            .collect::<Vec<String>>();
-        return Ok(res);
+/// `src/{label}.rs` をテンプレートのまま書き換えていなければ、
+/// 問題名・URL・制約などのヘッダーと入力形式に合わせた `input!` をつけたものに置き換える
+fn write_solution_file(
+    tests_path: &str,
+    problem: &ProblemMetadata,
+    constraints: Option<&Constraints>,
+    input: Option<&InputFormat>,
+) -> Result<(), String> {
+    let file = std::path::Path::new(tests_path)
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new(""))
+        .join(format!("src/{}.rs", problem.label));
+    match fs::read_to_string(&file) {
+        Ok(content) if content == CHILD_FILE_TEMPLATE.trim_start() => (),
+        _ => return Ok(()),
     }
 
-    Err("EOF".into())
+    let body = match input {
+        Some(input) => {
+            let uses = if input.uses_chars {
+                "use proconio::{input, marker::Chars};"
+            } else {
+                "use proconio::input;"
+            };
+            let declarations = input
+                .declarations
+                .iter()
+                .map(|d| format!("        {},", d))
+                .join("\n");
+            CHILD_FILE_WITH_INPUT_TEMPLATE
+                .trim_start()
+                .replace("{{use}}", uses)
+                .replace("{{input}}", &declarations)
+        }
+        None => CHILD_FILE_TEMPLATE.trim_start().to_string(),
+    };
+    let content = format!("{}\n{}", solution_header(problem, constraints), body);
+    fs::write(file, content).map_err(|_e| ErrorMessages::FailedWrite.into())
+}
+
+/// 解答ファイルの先頭につける `//!` のコメント
+fn solution_header(problem: &ProblemMetadata, constraints: Option<&Constraints>) -> String {
+    let mut lines = vec![format!(
+        "{} - {}",
+        problem.label.to_uppercase(),
+        problem.title.as_deref().unwrap_or_default()
+    )];
+    if let Some(url) = &problem.url {
+        lines.push(String::new());
+        lines.push(format!("<{}>", url));
+    }
+    if let (Some(time_limit), Some(memory_limit)) = (problem.time_limit_ms, problem.memory_limit_mb)
+    {
+        lines.push(String::new());
+        lines.push(format!(
+            "Time Limit: {} ms / Memory Limit: {} MB",
+            time_limit, memory_limit
+        ));
+    }
+    if let Some(constraints) = constraints.filter(|c| !c.items.is_empty()) {
+        lines.push(String::new());
+        lines.push(format!("# {}", constraints.heading));
+        lines.push(String::new());
+        lines.extend(constraints.items.iter().map(|item| format!("- {}", item)));
+    }
+    lines
+        .iter()
+        .map(|line| format!("//! {}", line).trim_end().to_string())
+        .join("\n")
+        + "\n"
+}
+
+fn write_test_file(path: &str, name: &str, sample_cnt: usize) -> Result<(), String> {
+    let mut test_file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(format!("{}/{}.rs", path, name))
+        .map_err(|_e| ErrorMessages::FailedCreateFile)?;
+    test_file
+        .write_all(
+            TEST_FILE_TEMPLATE
+                .to_string()
+                .add(
+                    (0..sample_cnt)
+                        .map(|i| TEST_FILE_CHILD_TEMPLATE.replace("{{num}}", &(i + 1).to_string()))
+                        .join("\n")
+                        .as_str(),
+                )
+                .replace("{{name}}", name)
+                .as_bytes(),
+        )
+        .map_err(|_e| ErrorMessages::FailedWrite.into())
+}
+
+/// `tests/{name}.rs` に、問題のディレクトリの各 case をリポジトリの checker で判定するテストを書き込む
+fn write_checker_test_file(
+    path: &str,
+    name: &str,
+    problem_dir: &std::path::Path,
+    cases: &[String],
+) -> Result<(), String> {
+    let content = TEST_FILE_WITH_CHECKER_TEMPLATE
+        // `"` や `\` を含むパスでも壊れないよう、エスケープした文字列リテラルにする
+        .replace(
+            "{{problem_dir}}",
+            &format!("{:?}", problem_dir.to_string_lossy()),
+        )
+        .add(
+            cases
+                .iter()
+                .map(|case| {
+                    TEST_FILE_WITH_CHECKER_CHILD_TEMPLATE
+                        .replace("{{ident}}", &case_ident(case))
+                        .replace("{{case}}", case)
+                })
+                .join("\n")
+                .as_str(),
+        )
+        .replace("{{name}}", name);
+    fs::write(format!("{}/{}.rs", path, name), content)
+        .map_err(|_e| ErrorMessages::FailedWrite.into())
+}
+
+/// コンテストの問題の一覧
+async fn fetch_task_list(
+    contest_url: &str,
+    client: &HttpClient,
+) -> Result<Vec<TaskListItem>, String> {
+    let (judge, contest_id) =
+        judge_of_url(contest_url).ok_or(format!("Unsupported URL `{}`", contest_url))?;
+    let tasks_url = judge.localized_url(&judge.tasks_url(&contest_id), config().lang);
+    let html = client.get_text(&tasks_url).await?;
+    judge.parse_task_list(&contest_id, &html)
 }
 
 /**
 example:
  ```
-   generate_sample_tests_file(
-       /*  url: */ "https://atcoder.jp/contests/abc000/tasks/abc000_a",
-       /* path: */ "abc-000/tests/a/a",
-       ...
+   write_sample_files(
+       /*    path: */ "abc-000/tests/a/a",
+       /* samples: */ &[("1 2\n".into(), "3\n".into())],
    )
  ```
 */
-async fn generate_sample_test_file(
-    url: &str,
-    path: &str,
-    cookie_headers: &HeaderMap,
-    client: &Client,
-) -> Result<usize, ()> {
-    let samples = fetch_sample_data(url, cookie_headers, client).await?;
-    let sample_cnt = samples.len();
-    for (idx, (input, output)) in samples.into_iter().enumerate() {
+fn write_sample_files(path: &str, samples: &[(String, String)]) -> Result<(), ()> {
+    for (idx, (input, output)) in samples.iter().enumerate() {
         // input のファイルを作って書き込む
         std::fs::OpenOptions::new()
             .truncate(true)
@@ -307,81 +1229,48 @@ async fn generate_sample_test_file(
             .write_all(output.as_bytes())
             .map_err(|_e| ())?;
     }
-    Ok(sample_cnt)
+    Ok(())
 }
 
-async fn fetch_sample_data(
-    url: &str,
-    cookie_headers: &HeaderMap,
-    client: &Client,
-) -> Result<Vec<(String, String)>, ()> {
+async fn fetch_task_page(url: &str, client: &HttpClient) -> Result<TaskPage, String> {
+    let (judge, _contest_id) = judge_of_url(url).ok_or(format!("Unsupported URL `{}`", url))?;
     let html = client
-        .get(url)
-        .headers(cookie_headers.clone())
-        .send()
-        .await
-        .map_err(|_e| ())?
-        .text()
-        .await
-        .map_err(|_e| ())?;
-    let doc = scraper::Html::parse_document(&html);
+        .get_text(&judge.localized_url(url, config().lang))
+        .await?;
+    Ok(judge.parse_task_page(&html, config().lang))
+}
 
-    Ok(extract_sample_data(doc))
-}
-
-static TASK_STATEMENT_SELECTOR: Lazy<scraper::Selector> =
-    Lazy::new(|| scraper::Selector::parse(r#"div[id="task-statement"]"#).unwrap());
-static PRE_SELECTOR: Lazy<scraper::Selector> =
-    Lazy::new(|| scraper::Selector::parse("pre").unwrap());
-fn extract_sample_data(doc: scraper::Html) -> Vec<(String, String)> {
-    let expected_strings = ["入力例", "出力例"];
-
-    let mut samples = Vec::new();
-    if let Some(task_statement) = doc.select(&TASK_STATEMENT_SELECTOR).next() {
-        for pre in task_statement.select(&PRE_SELECTOR) {
-            if let Some(h3) = pre.prev_sibling() {
-                if let Some(element) = h3.value().as_element() {
-                    if element.name() == "h3" && h3.has_children() {
-                        let child = h3.children().next().unwrap().value();
-                        if let Some(t) = child.as_text() {
-                            if expected_strings.iter().any(|&x| t.contains(x)) {
-                                samples.push(pre.text().collect::<String>());
-                            }
-                        }
-                    }
-                }
-            }
-        }
+fn report_sample_warnings(problem: &str, samples: &Samples) {
+    for warning in &samples.warnings {
+        eprintln!("Warning on Samples of `{}`: {}", problem, warning);
     }
-    samples
-        .chunks_exact(2)
-        .map(|v| (v.get(0).unwrap().clone(), v.get(1).unwrap().clone()))
-        .collect::<Vec<(String, String)>>()
 }
 
-fn create_cli() -> reqwest::Client {
-    reqwest::Client::builder()
-        .cookie_store(true)
-        .build()
-        .unwrap()
+fn create_cli() -> HttpClient {
+    HttpClient::new(get_local_cookie_header().unwrap_or_default())
 }
 
+/// `url` のページを取得する HttpClient (保存した AtCoder の cookie は AtCoder にだけ送る)
+fn create_cli_for(url: &str) -> HttpClient {
+    if url.starts_with(&format!("{}/", config().base_url)) {
+        create_cli()
+    } else {
+        HttpClient::new(HeaderMap::new())
+    }
+}
+
+/// 保存した cookie の `Cookie` ヘッダー (暗号化されている場合にパスフレーズを聞くのは 1 回だけにする)
 fn get_local_cookie_header() -> Option<HeaderMap> {
-    let file = std::fs::File::open(
-        dirs::home_dir()
-            .unwrap()
-            .join(".atcoder-create-contest-dir")
-            .join("cookie"),
-    )
-    .ok()?;
+    static COOKIE_HEADERS: Lazy<Option<HeaderMap>> = Lazy::new(|| {
+        let jar = CookieJar::load(config().base_host()).unwrap_or_else(|e| panic!("{}", e))?;
+        cookie_headers(&jar)
+    });
+    COOKIE_HEADERS.clone()
+}
 
-    let reader = std::io::BufReader::new(file);
+/// `jar` の AtCoder の cookie の `Cookie` ヘッダー
+fn cookie_headers(jar: &CookieJar) -> Option<HeaderMap> {
     let mut cookie_headers = HeaderMap::new();
-    reader.lines().for_each(|line| {
-        cookie_headers.insert(
-            COOKIE,
-            HeaderValue::from_str(line.unwrap().as_str()).unwrap(),
-        );
-    });
+    cookie_headers.insert(COOKIE, jar.header_value(config().base_host())?);
     Some(cookie_headers)
 }
@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    io::{Cursor, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{config::config, ErrorMessages};
+
+/// ローカルテスター (生成器・ビジュアライザ) を置くディレクトリ
+pub const TOOLS_DIR: &str = "tools";
+/// テストケースの入力を置くディレクトリ
+pub const IN_DIR: &str = "in";
+
+static ZIP_LINK_SELECTOR: Lazy<scraper::Selector> =
+    Lazy::new(|| scraper::Selector::parse(r#"a[href$=".zip"]"#).unwrap());
+
+/// 問題文にあるローカルテスターの zip の URL
+pub fn find_tools_url(root: scraper::ElementRef) -> Option<String> {
+    let href = root
+        .select(&ZIP_LINK_SELECTOR)
+        .filter_map(|a| a.value().attr("href"))
+        .next()?;
+    if href.starts_with('/') {
+        Some(format!("{}{}", config().base_url, href))
+    } else {
+        Some(href.to_string())
+    }
+}
+
+/// zip を `dest` に展開する
+///
+/// `tools/Cargo.toml` のようにすべてが 1 つのディレクトリに入っている場合はそのディレクトリを取り除く
+pub fn extract_tools_zip(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Invalid Zip: {}", e))?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).map_err(|e| e.to_string())?;
+        // `../` などで展開先の外を指すものは無視する
+        if let Some(path) = file.enclosed_name() {
+            entries.push((i, path.to_path_buf(), file.is_dir()));
+        }
+    }
+    let prefix = common_root(entries.iter().map(|(_, path, _)| path.as_path()));
+
+    for (i, path, is_dir) in entries {
+        let path = match &prefix {
+            Some(prefix) => path.strip_prefix(prefix).unwrap().to_path_buf(),
+            None => path,
+        };
+        if path.as_os_str().is_empty() {
+            continue;
+        }
+        let out = dest.join(&path);
+        if is_dir {
+            fs::create_dir_all(&out).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+            continue;
+        }
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+        }
+        let mut content = Vec::new();
+        archive
+            .by_index(i)
+            .and_then(|mut file| Ok(file.read_to_end(&mut content)?))
+            .map_err(|e| e.to_string())?;
+        fs::write(&out, content).map_err(|_e| ErrorMessages::FailedWrite)?;
+    }
+    Ok(())
+}
+
+/// すべてのパスが同じディレクトリの中にあればそのディレクトリ
+fn common_root<'a>(mut paths: impl Iterator<Item = &'a Path>) -> Option<PathBuf> {
+    let first = match paths.next()?.components().next()? {
+        Component::Normal(name) => PathBuf::from(name),
+        _ => return None,
+    };
+    let mut has_child = false;
+    for path in paths {
+        if !path.starts_with(&first) {
+            return None;
+        }
+        has_child |= path != first;
+    }
+    Some(first).filter(|_| has_child)
+}
+
+/// ディレクトリを中身ごとコピーする
+pub fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    for entry in fs::read_dir(from).map_err(|_e| ErrorMessages::FailedGet)? {
+        let entry = entry.map_err(|_e| ErrorMessages::FailedGet)?;
+        let path = entry.path();
+        if path.is_dir() {
+            copy_dir(&path, &to.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, to.join(entry.file_name())).map_err(|_e| ErrorMessages::FailedWrite)?;
+        }
+    }
+    Ok(())
+}
+
+/// `tools/` にあるものに合わせて `cargo gen` などのエイリアスを作る
+pub fn tools_aliases(tools_dir: &Path) -> String {
+    let mut aliases = vec![
+        format!(
+            r#"gen = "run --release --manifest-path {}/Cargo.toml --bin gen -- {}/seeds.txt""#,
+            TOOLS_DIR, TOOLS_DIR
+        ),
+        format!(
+            r#"vis = "run --release --manifest-path {}/Cargo.toml --bin vis --""#,
+            TOOLS_DIR
+        ),
+    ];
+    // インタラクティブな問題のみ
+    if tools_dir.join("src/bin/tester.rs").is_file() {
+        aliases.push(format!(
+            r#"tester = "run --release --manifest-path {}/Cargo.toml --bin tester --""#,
+            TOOLS_DIR
+        ));
+    }
+    aliases.join("\n") + "\n"
+}
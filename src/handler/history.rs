@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    handler::score::{SeedResult, Status, RESULTS_DIR},
+    ErrorMessages,
+};
+
+/// `results/` に保存する、`score` の実行履歴と各シードの最高スコア
+pub const HISTORY_FILE_NAME: &str = "history.toml";
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct History {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<Run>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub best: Vec<BestScore>,
+    /// 最後の実行を反映する前の `best` (`compare` で最後の実行と比べる)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_best: Vec<BestScore>,
+}
+
+/// `score` を 1 回実行した結果
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Run {
+    /// `results/` にある CSV のファイル名
+    pub file: String,
+    /// 解答のバージョン (`source_id`)
+    pub source: String,
+    pub total: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BestScore {
+    pub seed: u32,
+    pub score: i64,
+    pub source: String,
+    /// このスコアを出した `Run` の `file`
+    pub run: String,
+}
+
+/// 最高スコアを更新したシード
+pub struct NewBest {
+    pub seed: u32,
+    pub old: Option<i64>,
+    pub new: i64,
+}
+
+impl History {
+    fn path() -> std::path::PathBuf {
+        Path::new(RESULTS_DIR).join(HISTORY_FILE_NAME)
+    }
+
+    /// 保存していなければ空の履歴
+    pub fn load() -> Result<History, String> {
+        let path = Self::path();
+        if !path.is_file() {
+            return Ok(History::default());
+        }
+        let content = std::fs::read_to_string(&path).map_err(|_e| ErrorMessages::FailedGet)?;
+        toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        std::fs::create_dir_all(RESULTS_DIR).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(Self::path(), content).map_err(|_e| ErrorMessages::FailedWrite.into())
+    }
+
+    /// 実行結果を追加し、最高スコアを更新する (失敗したシードは更新しない)
+    pub fn record(&mut self, run: Run, results: &[SeedResult], minimize: bool) -> Vec<NewBest> {
+        self.previous_best = self.best.clone();
+        let mut best = self
+            .best
+            .drain(..)
+            .map(|best| (best.seed, best))
+            .collect::<BTreeMap<u32, BestScore>>();
+        let mut new_bests = Vec::new();
+        for result in results.iter().filter(|r| r.status == Status::Ok) {
+            let score = match result.score {
+                Some(score) => score,
+                None => continue,
+            };
+            let old = best.get(&result.seed).map(|best| best.score);
+            if old.is_some_and(|old| !is_better(score, old, minimize)) {
+                continue;
+            }
+            best.insert(
+                result.seed,
+                BestScore {
+                    seed: result.seed,
+                    score,
+                    source: run.source.clone(),
+                    run: run.file.clone(),
+                },
+            );
+            new_bests.push(NewBest {
+                seed: result.seed,
+                old,
+                new: score,
+            });
+        }
+        self.best = best.into_values().collect();
+        self.runs.push(run);
+        new_bests
+    }
+}
+
+pub fn is_better(score: i64, than: i64, minimize: bool) -> bool {
+    if minimize {
+        score < than
+    } else {
+        score > than
+    }
+}
+
+/// 解答のバージョン
+///
+/// git のコミットから変更されていなければ `git:{コミット}`、そうでなければ `sha256:{ファイルのハッシュ}`
+pub fn source_id(solution: &Path) -> Result<String, String> {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let solution_str = solution.to_str().unwrap();
+    let commit = git(&["rev-parse", "--short", "HEAD"]);
+    let changed = git(&["status", "--porcelain", "--", solution_str]);
+    if let (Some(commit), Some(changed)) = (commit, changed) {
+        if changed.is_empty() {
+            return Ok(format!("git:{}", commit));
+        }
+    }
+
+    let content = std::fs::read(solution).map_err(|_e| ErrorMessages::FailedGet)?;
+    let hash = Sha256::digest(&content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    Ok(format!("sha256:{}", &hash[..12]))
+}
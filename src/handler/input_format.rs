@@ -0,0 +1,415 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{ElementRef, Selector};
+
+static PRE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("pre").unwrap());
+static LI_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("li").unwrap());
+static VAR_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("var").unwrap());
+
+/// `A`, `A_1`, `A_{1,1}`, `A_N` など 1 つの変数
+static VAR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Za-z][A-Za-z0-9']*)(?:_(\{[^}]*\}|[A-Za-z0-9]+))?").unwrap());
+/// 負の数 (`-10^9 \leq A_i` など)
+static NEGATIVE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(^|[^\w})])\s*-\s*\d").unwrap());
+static PAREN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\([^()]*\)|（[^（）]*）").unwrap());
+static REAL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"実数|小数|(?i)real number|decimal").unwrap());
+static STRING_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"文字列|(?i)string").unwrap());
+
+pub const INPUT_HEADINGS: &[&str] = &["入力", "Input"];
+pub const CONSTRAINTS_HEADINGS: &[&str] = &["制約", "Constraints"];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+/// 問題文の入力形式から作った `proconio::input!` の中身
+pub struct InputFormat {
+    /// `n: usize` などの 1 行ずつの宣言
+    pub declarations: Vec<String>,
+    /// `proconio::marker::Chars` を使うかどうか
+    pub uses_chars: bool,
+}
+
+/// 「入力」の `<pre>` から `input!` の宣言を作る (型は「制約」から推測する)
+///
+/// 解釈できない行がある場合は `None` を返す
+pub fn parse_input_format(root: ElementRef) -> Option<InputFormat> {
+    let format = section(root, INPUT_HEADINGS)?
+        .select(&PRE_SELECTOR)
+        .next()?
+        .text()
+        .collect::<String>();
+    let constraints = section(root, CONSTRAINTS_HEADINGS)
+        .map(|section| {
+            section
+                .select(&LI_SELECTOR)
+                .map(Constraint::new)
+                .collect::<Vec<Constraint>>()
+        })
+        .unwrap_or_default();
+
+    let lines = format
+        .lines()
+        .map(parse_line)
+        .collect::<Option<Vec<Option<Line>>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<Line>>();
+    let mut declarations = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        match &lines[idx] {
+            Line::Vertical => return None,
+            Line::Tokens(tokens) => {
+                // `A_1`, `A_2`, `\vdots`, `A_N` のような縦に並ぶ形式
+                let mut next = idx + 1;
+                while next < lines.len() && same_shape(&lines[next], tokens) {
+                    next += 1;
+                }
+                let vertical = matches!(lines.get(next), Some(Line::Vertical))
+                    && lines.get(next + 1).is_some_and(|l| same_shape(l, tokens));
+                if vertical {
+                    let last = match &lines[next + 1] {
+                        Line::Tokens(last) => last,
+                        Line::Vertical => unreachable!(),
+                    };
+                    declarations.push(vertical_declaration(tokens, last, &constraints)?);
+                    idx = next + 2;
+                } else {
+                    declarations.extend(line_declarations(tokens, &constraints)?);
+                    idx += 1;
+                }
+            }
+        }
+    }
+    if declarations.is_empty() {
+        return None;
+    }
+    Some(InputFormat {
+        uses_chars: declarations.iter().any(|d| d.contains("Chars")),
+        declarations,
+    })
+}
+
+/// `headings` のいずれかを見出しに持つ section
+pub fn section<'a>(root: ElementRef<'a>, headings: &[&str]) -> Option<ElementRef<'a>> {
+    root.descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| matches!(e.value().name(), "h2" | "h3" | "h4"))
+        .find(|h| headings.contains(&h.text().collect::<String>().trim()))
+        .and_then(|h| h.parent().and_then(ElementRef::wrap))
+}
+
+enum Line {
+    Tokens(Vec<Token>),
+    /// `\vdots` や `:` だけの行
+    Vertical,
+}
+
+enum Token {
+    /// 空白で区切られた 1 つの変数 (`A_{1,1}` なら indices は `["1", "1"]`)
+    Var {
+        name: String,
+        indices: Vec<String>,
+    },
+    /// 空白なしで並んだ変数 (`c_{1,1}c_{1,2}`)
+    Concat {
+        name: String,
+        indices: Vec<String>,
+    },
+    Ellipsis,
+}
+
+/// 空行は `Some(None)`、変数として読めない文字があれば `None`
+fn parse_line(line: &str) -> Option<Option<Line>> {
+    let line = line
+        .replace("\\ldots", " ... ")
+        .replace("\\cdots", " ... ")
+        .replace("\\dots", " ... ")
+        .replace('…', " ... ")
+        .replace("\\vdots", ":")
+        .replace('⋮', ":")
+        .replace("\\ ", " ")
+        .replace("\\quad", " ");
+    let line = line.trim();
+    if line.is_empty() {
+        return Some(None);
+    }
+    if line.split_whitespace().all(|t| t == ":" || t == ".") {
+        return Some(Some(Line::Vertical));
+    }
+
+    let mut tokens = Vec::new();
+    for token in line.split_whitespace() {
+        if token.chars().all(|c| c == '.') {
+            tokens.push(Token::Ellipsis);
+            continue;
+        }
+        let vars = VAR_REGEX.captures_iter(token).collect::<Vec<_>>();
+        let covered = vars.iter().map(|c| c[0].len()).sum::<usize>();
+        if vars.is_empty() || covered != token.len() {
+            return None;
+        }
+        let var = |c: &regex::Captures| {
+            let indices = c
+                .get(2)
+                .map(|m| {
+                    m.as_str()
+                        .trim_start_matches('{')
+                        .trim_end_matches('}')
+                        .split(',')
+                        .map(|s| s.trim().to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            (c[1].to_string(), indices)
+        };
+        let (name, indices) = var(&vars[vars.len() - 1]);
+        if vars.len() == 1 {
+            tokens.push(Token::Var { name, indices });
+        } else {
+            tokens.push(Token::Concat { name, indices });
+        }
+    }
+    Some(Some(Line::Tokens(tokens)))
+}
+
+/// 制約の 1 項目
+struct Constraint {
+    /// 最初の `<var>` (「`S` は長さ `N` の文字列」の `S`)
+    subject: String,
+    text: String,
+}
+impl Constraint {
+    fn new(li: ElementRef) -> Self {
+        // `(1 \leq i \leq N)` のような添字の範囲は型の推測に使わない
+        let strip = |text: String| PAREN_REGEX.replace_all(&text, "").to_string();
+        let text = strip(li.text().collect());
+        let subject = li
+            .select(&VAR_SELECTOR)
+            .next()
+            .map(|var| strip(var.text().collect()))
+            .unwrap_or_else(|| text.clone());
+        Constraint { subject, text }
+    }
+}
+
+/// 変数の名前の並びが同じ行か (`A_1 B_1` と `A_N B_N` など)
+fn same_shape(line: &Line, tokens: &[Token]) -> bool {
+    let names = |tokens: &[Token]| {
+        tokens
+            .iter()
+            .map(|t| match t {
+                Token::Var { name, indices } => (name.clone(), indices.len(), false),
+                Token::Concat { name, indices } => (name.clone(), indices.len(), true),
+                Token::Ellipsis => (String::new(), 0, false),
+            })
+            .collect::<Vec<_>>()
+    };
+    match line {
+        Line::Tokens(other) => {
+            names(other) == names(tokens) && tokens.iter().any(|t| !index_of(t).is_empty())
+        }
+        Line::Vertical => false,
+    }
+}
+
+fn index_of(token: &Token) -> &[String] {
+    match token {
+        Token::Var { indices, .. } | Token::Concat { indices, .. } => indices,
+        Token::Ellipsis => &[],
+    }
+}
+
+/// 1 行で完結する宣言 (`N M` や `A_1 A_2 ... A_N`)
+fn line_declarations(tokens: &[Token], constraints: &[Constraint]) -> Option<Vec<String>> {
+    if tokens
+        .iter()
+        .all(|t| matches!(t, Token::Var { indices, .. } if indices.is_empty()))
+    {
+        return tokens
+            .iter()
+            .map(|t| match t {
+                Token::Var { name, .. } => Some(format!(
+                    "{}: {}",
+                    rust_name(name)?,
+                    var_type(name, constraints)
+                )),
+                _ => None,
+            })
+            .collect();
+    }
+    let (name, len) = row(tokens)?;
+    let declaration = if tokens.iter().any(|t| matches!(t, Token::Concat { .. })) {
+        format!("{}: Chars", rust_name(&name)?)
+    } else {
+        format!(
+            "{}: [{}; {}]",
+            rust_name(&name)?,
+            var_type(&name, constraints),
+            len
+        )
+    };
+    Some(vec![declaration])
+}
+
+/// `A_1 A_2 ... A_N` のような 1 行の並びの (変数名, 長さ)
+fn row(tokens: &[Token]) -> Option<(String, String)> {
+    if !tokens.iter().any(|t| matches!(t, Token::Ellipsis)) {
+        return None;
+    }
+    let mut names = tokens.iter().filter_map(|t| match t {
+        Token::Var { name, .. } | Token::Concat { name, .. } => Some(name),
+        Token::Ellipsis => None,
+    });
+    let name = names.next()?;
+    if !names.all(|n| n == name) {
+        return None;
+    }
+    let len = rust_expr(index_of(tokens.last()?).last()?)?;
+    Some((name.clone(), len))
+}
+
+/// `first` から `last` まで縦に並ぶ宣言
+fn vertical_declaration(
+    first: &[Token],
+    last: &[Token],
+    constraints: &[Constraint],
+) -> Option<String> {
+    let len = rust_expr(index_of(last.first()?).first()?)?;
+
+    // `A_{1,1} A_{1,2} ... A_{1,W}` が縦に並ぶ 2 次元の形式
+    if let Some((name, width)) = row(first) {
+        if index_of(first.first()?).len() != 2 {
+            return None;
+        }
+        return Some(if first.iter().any(|t| matches!(t, Token::Concat { .. })) {
+            format!("{}: [Chars; {}]", rust_name(&name)?, len)
+        } else {
+            format!(
+                "{}: [[{}; {}]; {}]",
+                rust_name(&name)?,
+                var_type(&name, constraints),
+                width,
+                len
+            )
+        });
+    }
+
+    let names = first
+        .iter()
+        .map(|t| match t {
+            Token::Var { name, indices } if indices.len() == 1 => Some(name.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<String>>>()?;
+    let types = names
+        .iter()
+        .map(|name| var_type(name, constraints))
+        .collect::<Vec<&str>>();
+    let name = rust_name(&names.concat())?;
+    Some(match types.len() {
+        1 => format!("{}: [{}; {}]", name, types[0], len),
+        _ => format!("{}: [({}); {}]", name, types.join(", "), len),
+    })
+}
+
+/// 制約からの型の推測 (負の数がありうるなら `i64`)
+///
+/// 文字列・実数かどうかはその変数が主語になっている制約だけから判断する
+fn var_type(name: &str, constraints: &[Constraint]) -> &'static str {
+    let subject_of = constraints
+        .iter()
+        .filter(|c| mentions(&c.subject, name))
+        .collect::<Vec<&Constraint>>();
+    if subject_of.iter().any(|c| STRING_REGEX.is_match(&c.text)) {
+        "String"
+    } else if subject_of.iter().any(|c| REAL_REGEX.is_match(&c.text)) {
+        "f64"
+    } else if constraints
+        .iter()
+        .any(|c| mentions(&c.text, name) && NEGATIVE_REGEX.is_match(&c.text))
+    {
+        "i64"
+    } else {
+        "usize"
+    }
+}
+
+/// `text` に変数 `name` が出てくるか (`N` は `N_i` や `2N` にはマッチし、`NM` や `N2` にはマッチしない)
+fn mentions(text: &str, name: &str) -> bool {
+    text.match_indices(name).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + name.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_alphabetic())
+            && !after.is_some_and(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+fn rust_name(name: &str) -> Option<String> {
+    let name = name.replace('\'', "_").to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        return Some(format!("{}_", name));
+    }
+    Some(name)
+}
+
+/// `N`, `N-1`, `2N`, `NM` などの添字を Rust の式にする (並んだ数と変数は積)
+fn rust_expr(index: &str) -> Option<String> {
+    let mut res = String::new();
+    let mut prev_operand = false;
+    for c in index.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '+' | '-' => {
+                res.push_str(&format!(" {} ", c));
+                prev_operand = false;
+            }
+            _ if c.is_ascii_alphabetic() => {
+                if prev_operand {
+                    res.push_str(" * ");
+                }
+                res.push(c.to_ascii_lowercase());
+                prev_operand = true;
+            }
+            _ if c.is_ascii_digit() => {
+                res.push(c);
+                prev_operand = true;
+            }
+            _ => return None,
+        }
+    }
+    if res.is_empty() {
+        return None;
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacent_operands_are_multiplied() {
+        assert_eq!(rust_expr("N").unwrap(), "n");
+        assert_eq!(rust_expr("N-1").unwrap(), "n - 1");
+        assert_eq!(rust_expr("2N").unwrap(), "2 * n");
+        assert_eq!(rust_expr("NM").unwrap(), "n * m");
+        assert_eq!(rust_expr("HW+1").unwrap(), "h * w + 1");
+    }
+
+    #[test]
+    fn mentions_only_the_whole_variable() {
+        assert!(mentions("N", "N"));
+        assert!(mentions("-10^9 \\leq A_i", "A"));
+        assert!(mentions("2N", "N"));
+        assert!(!mentions("NM", "N"));
+        assert!(!mentions("N2", "N"));
+    }
+}
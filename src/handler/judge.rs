@@ -0,0 +1,126 @@
+mod atcoder;
+mod codeforces;
+mod yukicoder;
+
+use itertools::Itertools;
+use scraper::{ElementRef, Node};
+
+use crate::{
+    config::Lang,
+    handler::{input_format::InputFormat, markdown::Constraints, samples::Samples},
+    Contests,
+};
+
+pub use self::{atcoder::AtCoder, codeforces::Codeforces, yukicoder::Yukicoder};
+
+/// 問題の一覧にある問題 1 つ
+pub struct TaskListItem {
+    /// 問題の一覧に書かれている `A`, `C1` など
+    pub label: String,
+    pub title: String,
+    pub url: String,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+}
+
+pub struct TaskPage {
+    pub samples: Samples,
+    /// Markdown に変換した問題文
+    pub statement: Option<String>,
+    /// 入力形式から作った `input!` の中身
+    pub input: Option<InputFormat>,
+    pub constraints: Option<Constraints>,
+    /// AHC のローカルテスターの zip の URL
+    pub tools_url: Option<String>,
+    /// 問題の一覧に制限が書かれていないジャッジで、問題ページから読み取った制限
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+}
+
+/// コンテストのページの URL と HTML の読み方
+///
+/// ページの取得は呼び出し側で行い、ここでは取得した HTML を読むだけにする
+pub trait Judge: Sync {
+    /// URL がこのジャッジのコンテスト (もしくはその問題) のものなら、コンテストの ID
+    fn parse_contest_id(&self, url: &str) -> Option<String>;
+
+    fn contest_url(&self, contest_id: &str) -> String;
+
+    /// 問題の一覧のページの URL
+    fn tasks_url(&self, contest_id: &str) -> String;
+
+    /// `lang` の言語で表示する URL
+    fn localized_url(&self, url: &str, _lang: Lang) -> String {
+        url.to_string()
+    }
+
+    /// 作成するディレクトリの名前と、ID から決まるならコンテストの種類
+    fn contest_dir(&self, contest_id: &str) -> (String, Option<Contests>);
+
+    /// 問題の数や label がコンテストごとに違い、問題の一覧から決める必要がある
+    fn labels_from_task_list(&self) -> bool {
+        false
+    }
+
+    /// `tasks_url(contest_id)` のページの問題の一覧
+    fn parse_task_list(&self, contest_id: &str, html: &str) -> Result<Vec<TaskListItem>, String>;
+
+    fn parse_task_page(&self, html: &str, lang: Lang) -> TaskPage;
+}
+
+static JUDGES: [&dyn Judge; 3] = [&AtCoder, &Codeforces, &Yukicoder];
+
+/// URL のジャッジとコンテストの ID
+pub fn judge_of_url(url: &str) -> Option<(&'static dyn Judge, String)> {
+    JUDGES
+        .iter()
+        .find_map(|judge| judge.parse_contest_id(url).map(|id| (*judge, id)))
+}
+
+/// "2 sec" や "2.5 sec" を ms に変換する
+pub fn parse_time_limit(text: &str) -> Option<u64> {
+    let (num, unit) = text.trim().split_once(' ')?;
+    let num: f64 = num.parse().ok()?;
+    match unit.trim() {
+        "sec" | "s" | "second" | "seconds" => Some((num * 1000.0).round() as u64),
+        "msec" | "ms" => Some(num.round() as u64),
+        _ => None,
+    }
+}
+
+/// "1024 MB" や "1 GB" を MB に変換する
+pub fn parse_memory_limit(text: &str) -> Option<u64> {
+    let (num, unit) = text.trim().split_once(' ')?;
+    let num: f64 = num.parse().ok()?;
+    match unit.trim() {
+        "MB" | "MiB" | "megabytes" => Some(num.round() as u64),
+        "GB" | "GiB" | "gigabytes" => Some((num * 1024.0).round() as u64),
+        "KB" | "KiB" | "kilobytes" => Some((num / 1024.0).round() as u64),
+        _ => None,
+    }
+}
+
+/// `<pre>` の中身 (行は `<br>` 区切りか、1 行ずつ `div.test-example-line` などに入っている)
+///
+/// 前後の空行と行末の空白は除き、最後は改行で終わる
+fn pre_text(pre: ElementRef) -> String {
+    let mut text = String::new();
+    for node in pre.children() {
+        match node.value() {
+            Node::Text(t) => text += t,
+            Node::Element(e) if e.name() == "br" => text.push('\n'),
+            Node::Element(_) => {
+                text.extend(ElementRef::wrap(node).unwrap().text());
+                text.push('\n');
+            }
+            _ => (),
+        }
+    }
+    let lines = text.lines().map(str::trim_end).collect_vec();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n") + "\n",
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,132 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    config::{config, Lang},
+    handler::{
+        heuristic::find_tools_url,
+        input_format::parse_input_format,
+        judge::{parse_memory_limit, parse_time_limit, Judge, TaskListItem, TaskPage},
+        markdown::{constraints_to_markdown, to_markdown},
+        samples::{extract_samples, statement_root},
+    },
+    parser::{format_contest_name, ContestKind},
+    Contests,
+};
+
+pub struct AtCoder;
+
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^https?://{}/contests/([^/?#]+).*$",
+        regex::escape(config().base_host())
+    ))
+    .unwrap()
+});
+
+static TABLE_SELECTOR: Lazy<scraper::Selector> =
+    Lazy::new(|| scraper::Selector::parse("table").unwrap());
+static TH_SELECTOR: Lazy<scraper::Selector> =
+    Lazy::new(|| scraper::Selector::parse("thead th").unwrap());
+static TR_SELECTOR: Lazy<scraper::Selector> =
+    Lazy::new(|| scraper::Selector::parse("tbody tr").unwrap());
+static TD_SELECTOR: Lazy<scraper::Selector> = Lazy::new(|| scraper::Selector::parse("td").unwrap());
+static A_SELECTOR: Lazy<scraper::Selector> = Lazy::new(|| scraper::Selector::parse("a").unwrap());
+
+impl Judge for AtCoder {
+    fn parse_contest_id(&self, url: &str) -> Option<String> {
+        URL_REGEX.captures(url).map(|c| c[1].to_string())
+    }
+
+    fn contest_url(&self, contest_id: &str) -> String {
+        format!("{}/contests/{}", config().base_url, contest_id)
+    }
+
+    fn tasks_url(&self, contest_id: &str) -> String {
+        format!("{}/tasks", self.contest_url(contest_id))
+    }
+
+    /// `--lang` に合わせて `?lang=en` などをつけた URL
+    fn localized_url(&self, url: &str, lang: Lang) -> String {
+        match lang.query_value() {
+            Some(lang) if url.contains('?') => format!("{}&lang={}", url, lang),
+            Some(lang) => format!("{}?lang={}", url, lang),
+            None => url.to_string(),
+        }
+    }
+
+    /// `abc100` なら `abc-100` と ABC、それ以外は種類がわからない
+    fn contest_dir(&self, contest_id: &str) -> (String, Option<Contests>) {
+        match format_contest_name(contest_id) {
+            ContestKind::AXC(kind, num) => (
+                format!("{}-{}", kind, num),
+                Some((kind.as_str(), num.as_str()).into()),
+            ),
+            ContestKind::Other(name) => (name, None),
+        }
+    }
+
+    /// 「問題名」の列がある表の各行
+    fn parse_task_list(&self, _contest_id: &str, html: &str) -> Result<Vec<TaskListItem>, String> {
+        let doc = scraper::Html::parse_document(html);
+
+        for table in doc.select(&TABLE_SELECTOR) {
+            let headers = table
+                .select(&TH_SELECTOR)
+                .map(|element| element.text().collect::<String>().trim().to_string())
+                .collect::<Vec<String>>();
+            let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+            let pos = match column(&["問題名", "Task Name"]) {
+                Some(p) => p,
+                None => continue,
+            };
+            let time_limit_pos = column(&["実行時間制限", "Time Limit"]);
+            let memory_limit_pos = column(&["メモリ制限", "Memory Limit"]);
+
+            let res = table
+                .select(&TR_SELECTOR)
+                .map(|tr_element| {
+                    let td_elements = tr_element
+                        .select(&TD_SELECTOR)
+                        .collect::<Vec<scraper::ElementRef>>();
+                    let a_element = td_elements[pos].select(&A_SELECTOR).next().unwrap();
+                    let link = a_element.value().attr("href").unwrap();
+                    let cell_text = |pos: Option<usize>| {
+                        pos.and_then(|p| td_elements.get(p))
+                            .map(|td| td.text().collect::<String>())
+                    };
+                    TaskListItem {
+                        // 問題名の前の列が `A` などの label
+                        label: cell_text(pos.checked_sub(1))
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string(),
+                        title: a_element.text().collect::<String>().trim().to_string(),
+                        url: config().base_url.clone() + link,
+                        time_limit_ms: cell_text(time_limit_pos).and_then(|t| parse_time_limit(&t)),
+                        memory_limit_mb: cell_text(memory_limit_pos)
+                            .and_then(|t| parse_memory_limit(&t)),
+                    }
+                })
+                .collect::<Vec<TaskListItem>>();
+            return Ok(res);
+        }
+
+        Err("EOF".into())
+    }
+
+    fn parse_task_page(&self, html: &str, lang: Lang) -> TaskPage {
+        let doc = scraper::Html::parse_document(html);
+
+        let root = statement_root(&doc, lang);
+        TaskPage {
+            samples: extract_samples(&doc, lang),
+            statement: root.map(to_markdown),
+            input: root.and_then(parse_input_format),
+            constraints: root.and_then(constraints_to_markdown),
+            tools_url: root.and_then(find_tools_url),
+            time_limit_ms: None,
+            memory_limit_mb: None,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{ElementRef, Selector};
+
+use crate::{
+    config::{config, Lang},
+    handler::{
+        judge::{parse_memory_limit, parse_time_limit, pre_text, Judge, TaskListItem, TaskPage},
+        samples::Samples,
+    },
+    Contests,
+};
+
+pub struct Codeforces;
+
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^https?://(?:www\.)?{}/contest/([0-9]+)(?:[/?#].*)?$",
+        regex::escape(config().codeforces_host())
+    ))
+    .unwrap()
+});
+
+/// 問題の一覧の `1 s, 256 MB`
+static LIMITS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([0-9.]+ s),\s*([0-9.]+ [KMG]B)").unwrap());
+
+static ROW_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("table.problems tr").unwrap());
+static TD_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("td").unwrap());
+static A_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("a").unwrap());
+static INPUT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("div.sample-test div.input pre").unwrap());
+static OUTPUT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("div.sample-test div.output pre").unwrap());
+
+impl Judge for Codeforces {
+    fn parse_contest_id(&self, url: &str) -> Option<String> {
+        URL_REGEX.captures(url).map(|c| c[1].to_string())
+    }
+
+    fn contest_url(&self, contest_id: &str) -> String {
+        format!("{}/contest/{}", config().codeforces_base_url, contest_id)
+    }
+
+    /// コンテストのトップページに問題の一覧がある
+    fn tasks_url(&self, contest_id: &str) -> String {
+        self.contest_url(contest_id)
+    }
+
+    /// 英語とロシア語しかないので、`--lang en` のときだけ `?locale=en` をつける
+    fn localized_url(&self, url: &str, lang: Lang) -> String {
+        match lang {
+            Lang::En if url.contains('?') => format!("{}&locale=en", url),
+            Lang::En => format!("{}?locale=en", url),
+            _ => url.to_string(),
+        }
+    }
+
+    fn contest_dir(&self, contest_id: &str) -> (String, Option<Contests>) {
+        (format!("cf-{}", contest_id), Some(Contests::CF))
+    }
+
+    /// `A`, `B`, `C1`, `C2` のように問題の数も label もコンテストごとに違う
+    fn labels_from_task_list(&self) -> bool {
+        true
+    }
+
+    /// `table.problems` の各行 (1 列目が label、2 列目が問題名と制限)
+    fn parse_task_list(&self, _contest_id: &str, html: &str) -> Result<Vec<TaskListItem>, String> {
+        let doc = scraper::Html::parse_document(html);
+        let tasks = doc
+            .select(&ROW_SELECTOR)
+            .filter_map(|row| {
+                let tds = row.select(&TD_SELECTOR).collect::<Vec<ElementRef>>();
+                let label = tds.first()?.text().collect::<String>().trim().to_string();
+                let a = tds.get(1)?.select(&A_SELECTOR).next()?;
+                let link = a.value().attr("href")?;
+                let limits = tds[1].text().collect::<String>();
+                let limits = LIMITS_REGEX.captures(&limits);
+                Some(TaskListItem {
+                    label,
+                    title: a.text().collect::<String>().trim().to_string(),
+                    url: config().codeforces_base_url.clone() + link,
+                    time_limit_ms: limits.as_ref().and_then(|c| parse_time_limit(&c[1])),
+                    memory_limit_mb: limits.as_ref().and_then(|c| parse_memory_limit(&c[2])),
+                })
+            })
+            .collect::<Vec<TaskListItem>>();
+        if tasks.is_empty() {
+            return Err("Problems are not Found in the Contest Page".into());
+        }
+        Ok(tasks)
+    }
+
+    /// `div.sample-test` の入力と出力を順に対応させる (問題文は変換しない)
+    fn parse_task_page(&self, html: &str, _lang: Lang) -> TaskPage {
+        let doc = scraper::Html::parse_document(html);
+        let inputs = doc.select(&INPUT_SELECTOR).map(pre_text).collect_vec();
+        let outputs = doc.select(&OUTPUT_SELECTOR).map(pre_text).collect_vec();
+
+        let mut warnings = Vec::new();
+        if inputs.is_empty() {
+            warnings.push("sample is not found".to_string());
+        }
+        if inputs.len() != outputs.len() {
+            warnings.push(format!(
+                "{} inputs and {} outputs are found",
+                inputs.len(),
+                outputs.len()
+            ));
+        }
+        TaskPage {
+            samples: Samples {
+                samples: inputs.into_iter().zip(outputs).collect(),
+                warnings,
+            },
+            statement: None,
+            input: None,
+            constraints: None,
+            tools_url: None,
+            time_limit_ms: None,
+            memory_limit_mb: None,
+        }
+    }
+}
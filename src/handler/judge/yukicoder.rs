@@ -0,0 +1,162 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{
+    config::{config, Lang},
+    handler::{
+        judge::{parse_memory_limit, parse_time_limit, pre_text, Judge, TaskListItem, TaskPage},
+        samples::Samples,
+    },
+    Contests,
+};
+
+pub struct Yukicoder;
+
+/// コンテスト (`/contests/300`) と、コンテストに属さない問題 (`/problems/no/1234`)
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^https?://{}/(?:contests/([0-9]+)|problems/(no/[0-9]+))(?:[/?#].*)?$",
+        regex::escape(config().yukicoder_host())
+    ))
+    .unwrap()
+});
+
+/// 問題ページの `実行時間制限 : 1ケース 2.000秒 / メモリ制限 : 512 MB`
+static TIME_LIMIT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"実行時間制限\s*:\s*1ケース\s*([0-9.]+)\s*秒").unwrap());
+static MEMORY_LIMIT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"メモリ制限\s*:\s*([0-9.]+)\s*([KMG]B)").unwrap());
+
+static ROW_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("table tbody tr").unwrap());
+static TD_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("td").unwrap());
+static PROBLEM_LINK_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse(r#"a[href*="/problems/no/"]"#).unwrap());
+static CONTENT_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("#content").unwrap());
+static TITLE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("#content h3").unwrap());
+static SAMPLE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("div.sample").unwrap());
+static PRE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("pre").unwrap());
+
+impl Judge for Yukicoder {
+    fn parse_contest_id(&self, url: &str) -> Option<String> {
+        let c = URL_REGEX.captures(url)?;
+        c.get(1)
+            .or_else(|| c.get(2))
+            .map(|m| m.as_str().to_string())
+    }
+
+    fn contest_url(&self, contest_id: &str) -> String {
+        if contest_id.starts_with("no/") {
+            format!("{}/problems/{}", config().yukicoder_base_url, contest_id)
+        } else {
+            format!("{}/contests/{}", config().yukicoder_base_url, contest_id)
+        }
+    }
+
+    /// コンテストはトップページに問題の一覧があり、問題 1 つの場合は問題ページそのもの
+    fn tasks_url(&self, contest_id: &str) -> String {
+        self.contest_url(contest_id)
+    }
+
+    /// `yuki-300` や `yuki-no-1234`
+    fn contest_dir(&self, contest_id: &str) -> (String, Option<Contests>) {
+        (
+            format!("yuki-{}", contest_id.replace('/', "-")),
+            Some(Contests::YUKI),
+        )
+    }
+
+    fn labels_from_task_list(&self) -> bool {
+        true
+    }
+
+    /// コンテストの問題の表の各行 (1 列目が label)、問題 1 つの場合は label が `A` の 1 問
+    fn parse_task_list(&self, contest_id: &str, html: &str) -> Result<Vec<TaskListItem>, String> {
+        let doc = Html::parse_document(html);
+        if contest_id.starts_with("no/") {
+            let title = doc
+                .select(&TITLE_SELECTOR)
+                .next()
+                .ok_or("Problem Title is not Found")?;
+            let (time_limit_ms, memory_limit_mb) = parse_limits(&doc);
+            return Ok(vec![TaskListItem {
+                label: "A".into(),
+                title: title.text().collect::<String>().trim().to_string(),
+                url: self.contest_url(contest_id),
+                time_limit_ms,
+                memory_limit_mb,
+            }]);
+        }
+
+        let tasks = doc
+            .select(&ROW_SELECTOR)
+            .filter_map(|row| {
+                let label = row.select(&TD_SELECTOR).next()?.text().collect::<String>();
+                let a = row.select(&PROBLEM_LINK_SELECTOR).next()?;
+                let link = a.value().attr("href")?;
+                Some(TaskListItem {
+                    label: label.trim().to_string(),
+                    title: a.text().collect::<String>().trim().to_string(),
+                    url: if link.starts_with('/') {
+                        config().yukicoder_base_url.clone() + link
+                    } else {
+                        link.to_string()
+                    },
+                    time_limit_ms: None,
+                    memory_limit_mb: None,
+                })
+            })
+            .collect::<Vec<TaskListItem>>();
+        if tasks.is_empty() {
+            return Err("Problems are not Found in the Contest Page".into());
+        }
+        Ok(tasks)
+    }
+
+    /// `div.sample` ごとに入力と出力の `pre` が 1 つずつある
+    fn parse_task_page(&self, html: &str, _lang: Lang) -> TaskPage {
+        let doc = Html::parse_document(html);
+        let mut samples = Vec::new();
+        let mut warnings = Vec::new();
+        for (i, sample) in doc.select(&SAMPLE_SELECTOR).enumerate() {
+            let pres = sample.select(&PRE_SELECTOR).collect::<Vec<ElementRef>>();
+            match pres[..] {
+                [input, output] => samples.push((pre_text(input), pre_text(output))),
+                _ => warnings.push(format!(
+                    "sample {} has {} blocks (expected input and output)",
+                    i + 1,
+                    pres.len()
+                )),
+            }
+        }
+        if samples.is_empty() && warnings.is_empty() {
+            warnings.push("sample is not found".to_string());
+        }
+
+        let (time_limit_ms, memory_limit_mb) = parse_limits(&doc);
+        TaskPage {
+            samples: Samples { samples, warnings },
+            statement: None,
+            input: None,
+            constraints: None,
+            tools_url: None,
+            time_limit_ms,
+            memory_limit_mb,
+        }
+    }
+}
+
+/// 問題ページの実行時間制限 (ms) とメモリ制限 (MB)
+fn parse_limits(doc: &Html) -> (Option<u64>, Option<u64>) {
+    let text = match doc.select(&CONTENT_SELECTOR).next() {
+        Some(content) => content.text().collect::<String>(),
+        None => return (None, None),
+    };
+    let time_limit_ms = TIME_LIMIT_REGEX
+        .captures(&text)
+        .and_then(|c| parse_time_limit(&format!("{} s", &c[1])));
+    let memory_limit_mb = MEMORY_LIMIT_REGEX
+        .captures(&text)
+        .and_then(|c| parse_memory_limit(&format!("{} {}", &c[1], &c[2])));
+    (time_limit_ms, memory_limit_mb)
+}
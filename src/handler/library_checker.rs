@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::ErrorMessages;
+
+/// `generate.py` が問題のディレクトリに作る checker
+pub const CHECKER_FILE_NAME: &str = "checker";
+
+/// 問題のディレクトリの `info.toml` (使うところだけ)
+#[derive(Deserialize)]
+pub struct ProblemInfo {
+    pub title: Option<String>,
+    /// 実行時間制限 (秒)
+    pub timelimit: Option<f64>,
+}
+
+impl ProblemInfo {
+    pub fn load(problem_dir: &Path) -> Result<ProblemInfo, String> {
+        let path = problem_dir.join("info.toml");
+        let content = std::fs::read_to_string(&path).map_err(|_e| ErrorMessages::FailedGet)?;
+        toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn time_limit_ms(&self) -> Option<u64> {
+        self.timelimit.map(|sec| (sec * 1000.0).round() as u64)
+    }
+}
+
+/// `{repo}/{カテゴリ}/{name}/info.toml` がある問題のディレクトリ
+pub fn find_problem_dir(repo: &Path, name: &str) -> Result<PathBuf, String> {
+    let categories = std::fs::read_dir(repo)
+        .map_err(|_e| format!("Repository `{}` is not Found", repo.display()))?;
+    categories
+        .flatten()
+        .map(|category| category.path().join(name))
+        .find(|dir| dir.join("info.toml").is_file())
+        .ok_or(format!(
+            "Problem `{}` is not Found in {}",
+            name,
+            repo.display()
+        ))
+}
+
+/// リポジトリの `generate.py` で入力・出力と checker を作る
+pub async fn generate_cases(repo: &Path, name: &str) -> Result<(), String> {
+    let output = tokio::process::Command::new("python3")
+        .args(["generate.py", "-p", name])
+        .current_dir(repo)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to Run generate.py: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to Generate Cases (`python3 generate.py -p {}`):\n{}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ));
+    }
+    Ok(())
+}
+
+/// `in/{case}.in` と `out/{case}.out` の両方がある case の名前 (名前順)
+pub fn list_cases(problem_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = std::fs::read_dir(problem_dir.join("in"))
+        .map_err(|_e| format!("Cases are not Found in {}", problem_dir.display()))?;
+    let mut cases = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|case| {
+            problem_dir
+                .join("out")
+                .join(format!("{}.out", case))
+                .is_file()
+        })
+        .collect::<Vec<String>>();
+    cases.sort();
+    Ok(cases)
+}
+
+/// テストの関数名に使える名前 (`example_00` など、それ以外の文字は `_` にする)
+pub fn case_ident(case: &str) -> String {
+    case.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,254 @@
+use ego_tree::NodeRef;
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use scraper::{ElementRef, Node, Selector};
+
+use super::input_format::{section, CONSTRAINTS_HEADINGS};
+
+static LI_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("li").unwrap());
+static HEADING_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("h2, h3, h4").unwrap());
+static TEX_ANNOTATION_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse(r#"annotation[encoding="application/x-tex"]"#).unwrap());
+
+/// 問題文の HTML を Markdown に変換する
+///
+/// `<var>` と KaTeX で描画済みの数式は `$...$` の TeX に戻す
+pub fn to_markdown(root: ElementRef) -> String {
+    let mut out = String::new();
+    render_children(*root, &mut out);
+    normalize(&out)
+}
+
+/// 問題文の「制約」の見出しと各項目
+pub struct Constraints {
+    pub heading: String,
+    /// 1 項目ずつ Markdown にしたもの
+    pub items: Vec<String>,
+}
+
+pub fn constraints_to_markdown(root: ElementRef) -> Option<Constraints> {
+    let section = section(root, CONSTRAINTS_HEADINGS)?;
+    let heading = section
+        .select(&HEADING_SELECTOR)
+        .next()?
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    let items = section
+        .select(&LI_SELECTOR)
+        .map(|li| {
+            let mut text = String::new();
+            render_children(*li, &mut text);
+            normalize(&text).split_whitespace().join(" ")
+        })
+        .collect();
+    Some(Constraints { heading, items })
+}
+
+fn render_children(node: NodeRef<Node>, out: &mut String) {
+    for child in node.children() {
+        render_node(child, out);
+    }
+}
+
+fn render_node(node: NodeRef<Node>, out: &mut String) {
+    let element = match node.value() {
+        Node::Text(text) => {
+            out.push_str(&collapse_whitespace(
+                &text.replace("\\(", "$").replace("\\)", "$"),
+            ));
+            return;
+        }
+        Node::Element(element) => element,
+        _ => return,
+    };
+    let has_class = |name: &str| element.classes().any(|class| class == name);
+
+    match element.name() {
+        "script" | "style" | "button" => (),
+        _ if has_class("btn-copy") || has_class("katex-html") => (),
+        _ if has_class("katex") => {
+            let tex = ElementRef::wrap(node)
+                .and_then(|e| e.select(&TEX_ANNOTATION_SELECTOR).next())
+                .map(|annotation| annotation.text().collect::<String>());
+            match tex {
+                Some(tex) => out.push_str(&format!("${}$", tex.trim())),
+                None => render_children(node, out),
+            }
+        }
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            let level = match name {
+                "h1" | "h2" | "h3" => 2,
+                "h4" => 3,
+                _ => 4,
+            };
+            let mut heading = String::new();
+            render_children(node, &mut heading);
+            block(out);
+            out.push_str(&format!("{} {}", "#".repeat(level), heading.trim()));
+            block(out);
+        }
+        "p" | "div" | "section" | "blockquote" => {
+            block(out);
+            render_children(node, out);
+            block(out);
+        }
+        "br" => out.push_str("  \n"),
+        "hr" => {
+            block(out);
+            out.push_str("---");
+            block(out);
+        }
+        "var" => out.push_str(&format!("${}$", text_of(node).trim())),
+        "code" => out.push_str(&format!("`{}`", text_of(node))),
+        "strong" | "b" => {
+            out.push_str("**");
+            render_children(node, out);
+            out.push_str("**");
+        }
+        "em" | "i" => {
+            out.push('*');
+            render_children(node, out);
+            out.push('*');
+        }
+        "a" => {
+            let mut text = String::new();
+            render_children(node, &mut text);
+            match element.attr("href") {
+                Some(href) => out.push_str(&format!("[{}]({})", text.trim(), href)),
+                None => out.push_str(&text),
+            }
+        }
+        "img" => out.push_str(&format!(
+            "![{}]({})",
+            element.attr("alt").unwrap_or_default(),
+            element.attr("src").unwrap_or_default()
+        )),
+        "pre" => {
+            let text = text_of(node);
+            block(out);
+            out.push_str("```\n");
+            out.push_str(&text);
+            if !text.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("```");
+            block(out);
+        }
+        list @ ("ul" | "ol") => {
+            block(out);
+            let items = node
+                .children()
+                .filter(|child| matches!(child.value(), Node::Element(e) if e.name() == "li"));
+            for (idx, item) in items.enumerate() {
+                let mut text = String::new();
+                render_children(item, &mut text);
+                let marker = match list {
+                    "ol" => format!("{}. ", idx + 1),
+                    _ => "- ".to_string(),
+                };
+                out.push_str(&marker);
+                out.push_str(&normalize(&text).trim_end().replace('\n', "\n  "));
+                out.push('\n');
+            }
+            block(out);
+        }
+        "table" => {
+            block(out);
+            render_table(node, out);
+            block(out);
+        }
+        _ => render_children(node, out),
+    }
+}
+
+fn render_table(table: NodeRef<Node>, out: &mut String) {
+    let rows = table
+        .descendants()
+        .filter(|node| matches!(node.value(), Node::Element(e) if e.name() == "tr"))
+        .map(|tr| {
+            tr.children()
+                .filter(|node| matches!(node.value(), Node::Element(e) if e.name() == "td" || e.name() == "th"))
+                .map(|cell| {
+                    let mut text = String::new();
+                    render_children(cell, &mut text);
+                    normalize(&text).replace('\n', " ").replace('|', "\\|")
+                })
+                .collect::<Vec<String>>()
+        })
+        .filter(|cells| !cells.is_empty())
+        .collect::<Vec<Vec<String>>>();
+    for (idx, row) in rows.iter().enumerate() {
+        out.push_str(&format!("| {} |\n", row.join(" | ")));
+        if idx == 0 {
+            out.push_str(&format!("|{}\n", " --- |".repeat(row.len())));
+        }
+    }
+}
+
+fn text_of(node: NodeRef<Node>) -> String {
+    node.descendants()
+        .filter_map(|n| n.value().as_text().map(|t| t.to_string()))
+        .collect()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut res = String::new();
+    let mut prev_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !prev_space {
+                res.push(' ');
+            }
+            prev_space = true;
+        } else {
+            res.push(c);
+            prev_space = false;
+        }
+    }
+    res
+}
+
+/// ブロックの区切りとして空行を入れる
+fn block(out: &mut String) {
+    if out.trim().is_empty() {
+        return;
+    }
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// 行頭の空白と連続する空行を取り除く (コードブロックの中はそのまま)
+fn normalize(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            lines.push(line.trim_start().to_string());
+            continue;
+        }
+        if in_code {
+            lines.push(line.to_string());
+            continue;
+        }
+        let line = if line.ends_with("  ") {
+            format!("{}  ", line.trim())
+        } else {
+            line.trim().to_string()
+        };
+        // `Option::is_none_or` は新しい Rust でしか使えない
+        #[allow(clippy::unnecessary_map_or)]
+        let after_blank = lines.last().map_or(true, |l: &String| l.is_empty());
+        if line.is_empty() && after_blank {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n") + "\n"
+}
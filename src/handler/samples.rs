@@ -0,0 +1,154 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::config::Lang;
+
+static TASK_STATEMENT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse(r#"div[id="task-statement"]"#).unwrap());
+static LANG_JA_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("span.lang-ja").unwrap());
+static LANG_EN_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("span.lang-en").unwrap());
+
+static SAMPLE_HEADING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(入力例|出力例|Sample Input|Sample Output)\s*([0-9０-９]*)").unwrap()
+});
+
+pub struct Samples {
+    /// 番号順に並べた (入力, 出力) の組
+    pub samples: Vec<(String, String)>,
+    /// 入力例と出力例の対応が取れなかったものなど
+    pub warnings: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SampleKind {
+    Input,
+    Output,
+}
+
+/// 問題文のうち `lang` の言語の部分 (`span.lang-ja` / `span.lang-en`)
+///
+/// `Lang::Auto` のときは日本語があれば日本語、なければ英語の部分を返す
+/// 言語ごとに分かれていない問題文はそのまま返す
+pub fn statement_root(doc: &Html, lang: Lang) -> Option<ElementRef<'_>> {
+    let task_statement = doc.select(&TASK_STATEMENT_SELECTOR).next()?;
+    let ja = task_statement.select(&LANG_JA_SELECTOR).next();
+    let en = task_statement.select(&LANG_EN_SELECTOR).next();
+    let root = match lang {
+        Lang::Ja => ja,
+        Lang::En => en,
+        Lang::Auto => ja.filter(|e| has_text(e)).or(en),
+    };
+    Some(root.unwrap_or(task_statement))
+}
+
+fn has_text(element: &ElementRef) -> bool {
+    element.text().any(|t| !t.trim().is_empty())
+}
+
+/// 問題文の見出し (`入力例 1` / `Sample Input 1` など) ごとに直後の `<pre>` を取り出し、番号で入力と出力を対応させる
+pub fn extract_samples(doc: &Html, lang: Lang) -> Samples {
+    let root = match statement_root(doc, lang) {
+        Some(root) => root,
+        None => {
+            return Samples {
+                samples: Vec::new(),
+                warnings: vec!["task statement is not found".into()],
+            }
+        }
+    };
+    let mut samples = extract_samples_from(root);
+    // 日本語の問題文にサンプルがない場合は英語の方を見る
+    if lang == Lang::Auto && samples.samples.is_empty() {
+        if let Some(en) = statement_root(doc, Lang::En).filter(|en| *en != root) {
+            let en_samples = extract_samples_from(en);
+            if !en_samples.samples.is_empty() {
+                samples = en_samples;
+            }
+        }
+    }
+    samples
+}
+
+fn extract_samples_from(root: ElementRef) -> Samples {
+    let mut warnings = Vec::new();
+    let mut inputs = BTreeMap::new();
+    let mut outputs = BTreeMap::new();
+    let mut pending: Option<(SampleKind, usize, String)> = None;
+    for element in root.descendants().filter_map(ElementRef::wrap) {
+        match element.value().name() {
+            "h2" | "h3" | "h4" => {
+                let heading = normalize_whitespace(&element.text().collect::<String>());
+                if let Some((_, _, prev)) = pending.take() {
+                    warnings.push(format!("`{}` has no <pre>", prev));
+                }
+                if let Some(c) = SAMPLE_HEADING_REGEX.captures(&heading) {
+                    let kind = match &c[1] {
+                        "入力例" | "Sample Input" => SampleKind::Input,
+                        _ => SampleKind::Output,
+                    };
+                    let num = match parse_number(&c[2]) {
+                        Some(num) => num,
+                        // 番号のない見出しは出てきた順に番号をつける
+                        None => match kind {
+                            SampleKind::Input => inputs.len() + 1,
+                            SampleKind::Output => outputs.len() + 1,
+                        },
+                    };
+                    pending = Some((kind, num, heading));
+                }
+            }
+            "pre" => {
+                if let Some((kind, num, heading)) = pending.take() {
+                    let map = match kind {
+                        SampleKind::Input => &mut inputs,
+                        SampleKind::Output => &mut outputs,
+                    };
+                    match map.entry(num) {
+                        Entry::Vacant(entry) => {
+                            entry.insert(element.text().collect::<String>());
+                        }
+                        Entry::Occupied(_) => {
+                            warnings.push(format!("`{}` is duplicated", heading));
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if let Some((_, _, heading)) = pending {
+        warnings.push(format!("`{}` has no <pre>", heading));
+    }
+
+    let mut samples = Vec::new();
+    for (num, input) in inputs {
+        match outputs.remove(&num) {
+            Some(output) => samples.push((input, output)),
+            None => warnings.push(format!("Sample Input {} has no Sample Output", num)),
+        }
+    }
+    for num in outputs.keys() {
+        warnings.push(format!("Sample Output {} has no Sample Input", num));
+    }
+
+    Samples { samples, warnings }
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// 全角数字も受け付ける
+fn parse_number(text: &str) -> Option<usize> {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap(),
+            _ => c,
+        })
+        .collect::<String>()
+        .parse()
+        .ok()
+}
@@ -0,0 +1,318 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    handler::heuristic::{IN_DIR, TOOLS_DIR},
+    ErrorMessages,
+};
+
+/// 解答の出力を置くディレクトリ
+pub const OUT_DIR: &str = "out";
+/// `score` の結果の CSV を置くディレクトリ
+pub const RESULTS_DIR: &str = "results";
+
+/// ローカルテスターは `Score = 1234` を出力する
+static SCORE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^Score\s*=\s*(-?[0-9]+)\s*$").unwrap());
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok,
+    /// 実行時間の上限を超えた
+    Tle,
+    /// 解答が 0 以外で終了した
+    Re,
+    /// スコアを計算できなかった
+    Err,
+}
+impl Status {
+    pub fn value<'a>(&self) -> &'a str {
+        match *self {
+            Status::Ok => "OK",
+            Status::Tle => "TLE",
+            Status::Re => "RE",
+            Status::Err => "ERR",
+        }
+    }
+
+    fn from_value(value: &str) -> Option<Status> {
+        match value {
+            "OK" => Some(Status::Ok),
+            "TLE" => Some(Status::Tle),
+            "RE" => Some(Status::Re),
+            "ERR" => Some(Status::Err),
+            _ => None,
+        }
+    }
+}
+
+pub struct SeedResult {
+    pub seed: u32,
+    pub status: Status,
+    /// 失敗したシードは `None` (合計などでは 0 点として扱う)
+    pub score: Option<i64>,
+    pub time_ms: u64,
+}
+
+/// ビルドした解答とローカルテスター
+pub struct Runner {
+    solution: PathBuf,
+    /// インタラクティブな問題では `tester`、それ以外では `vis`
+    scorer: PathBuf,
+    interactive: bool,
+    time_limit: Duration,
+}
+
+/// `in/0000.txt` のような、シードごとのファイル
+pub fn seed_file(dir: &str, seed: u32) -> PathBuf {
+    Path::new(dir).join(format!("{:04}.txt", seed))
+}
+
+/// `in/` にある入力のシード
+pub fn list_seeds() -> Vec<u32> {
+    fs::read_dir(IN_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_suffix(".txt")?.parse().ok()
+                })
+                .sorted()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 解答 (`--bin {problem}`) とローカルテスターを `--release` でビルドする
+pub async fn build_runner(problem: &str, time_limit: Duration) -> Result<Runner, String> {
+    let tools_dir = Path::new(TOOLS_DIR);
+    if !tools_dir.join("Cargo.toml").is_file() {
+        return Err(format!("Local Tester is not Found on `{}`", TOOLS_DIR));
+    }
+    let interactive = tools_dir.join("src/bin/tester.rs").is_file();
+    let scorer = if interactive { "tester" } else { "vis" };
+
+    cargo_build(&["--bin", problem]).await?;
+    let manifest = tools_dir.join("Cargo.toml");
+    cargo_build(&[
+        "--manifest-path",
+        manifest.to_str().unwrap(),
+        "--bin",
+        scorer,
+    ])
+    .await?;
+    Ok(Runner {
+        solution: target_dir(Path::new(".")).join("release").join(problem),
+        scorer: target_dir(tools_dir).join("release").join(scorer),
+        interactive,
+        time_limit,
+    })
+}
+
+async fn cargo_build(args: &[&str]) -> Result<(), String> {
+    let status = tokio::process::Command::new("cargo")
+        .args(["build", "--release"])
+        .args(args)
+        .status()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!(
+            "Failed to Build (`cargo build --release {}`)",
+            args.join(" ")
+        ));
+    }
+    Ok(())
+}
+
+/// `CARGO_TARGET_DIR` があればそれを、なければ `{manifest_dir}/target` を使う
+fn target_dir(manifest_dir: &Path) -> PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => manifest_dir.join("target"),
+    }
+}
+
+/// `jobs` 個ずつ並列に実行し、終わったものから表示する (結果はシードの順に並べて返す)
+pub async fn run_seeds(
+    runner: &Runner,
+    seeds: &[u32],
+    jobs: usize,
+) -> Result<Vec<SeedResult>, String> {
+    fs::create_dir_all(OUT_DIR).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    let mut results = futures::stream::iter(seeds.iter().copied())
+        .map(|seed| async move {
+            let result = run_seed(runner, seed).await;
+            match (result.status, result.score) {
+                (Status::Ok, Some(score)) => {
+                    println!("{:04}: {} ({} ms)", seed, score, result.time_ms)
+                }
+                (status, _) => println!("{:04}: {} ({} ms)", seed, status.value(), result.time_ms),
+            }
+            result
+        })
+        .buffer_unordered(jobs.max(1))
+        .collect::<Vec<SeedResult>>()
+        .await;
+    results.sort_by_key(|result| result.seed);
+    Ok(results)
+}
+
+async fn run_seed(runner: &Runner, seed: u32) -> SeedResult {
+    let input = seed_file(IN_DIR, seed);
+    let output = seed_file(OUT_DIR, seed);
+    let start = Instant::now();
+    let run = run_solution(runner, &input, &output).await;
+    let time_ms = start.elapsed().as_millis() as u64;
+
+    let (status, score) = match run {
+        Ok(None) => (Status::Tle, None),
+        Ok(Some(output)) if !output.status.success() => (Status::Re, None),
+        // インタラクティブな問題では `tester` がスコアを出力する
+        Ok(Some(output)) if runner.interactive => parse_score(&output),
+        Ok(Some(_)) => match tokio::process::Command::new(&runner.scorer)
+            .arg(&input)
+            .arg(&output)
+            .output()
+            .await
+        {
+            Ok(output) => parse_score(&output),
+            Err(_) => (Status::Err, None),
+        },
+        Err(_) => (Status::Err, None),
+    };
+    SeedResult {
+        seed,
+        status,
+        score,
+        time_ms,
+    }
+}
+
+/// 実行時間の上限を超えたら `Ok(None)` (プロセスは終了させる)
+async fn run_solution(
+    runner: &Runner,
+    input: &Path,
+    output: &Path,
+) -> std::io::Result<Option<std::process::Output>> {
+    let mut command = if runner.interactive {
+        let mut command = tokio::process::Command::new(&runner.scorer);
+        command.arg(&runner.solution).stderr(Stdio::piped());
+        command
+    } else {
+        let mut command = tokio::process::Command::new(&runner.solution);
+        command.stderr(Stdio::null());
+        command
+    };
+    let child = command
+        .stdin(fs::File::open(input)?)
+        .stdout(fs::File::create(output)?)
+        .kill_on_drop(true)
+        .spawn()?;
+    match tokio::time::timeout(runner.time_limit, child.wait_with_output()).await {
+        Ok(output) => output.map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_score(output: &std::process::Output) -> (Status, Option<i64>) {
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    match SCORE_REGEX
+        .captures(&text)
+        .and_then(|c| c[1].parse::<i64>().ok())
+    {
+        Some(score) => (Status::Ok, Some(score)),
+        None => (Status::Err, None),
+    }
+}
+
+pub fn print_summary(results: &[SeedResult]) {
+    let score = |result: &SeedResult| result.score.unwrap_or(0);
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let total: i64 = results.iter().map(score).sum();
+    println!();
+    println!(
+        "Seeds: {} (OK: {}, TLE: {}, RE: {}, ERR: {})",
+        results.len(),
+        count(Status::Ok),
+        count(Status::Tle),
+        count(Status::Re),
+        count(Status::Err)
+    );
+    println!("Total: {}", total);
+    println!("Mean:  {:.2}", total as f64 / results.len().max(1) as f64);
+    if let Some(min) = results.iter().min_by_key(|r| score(r)) {
+        println!("Min:   {} (seed {:04})", score(min), min.seed);
+    }
+    if let Some(max) = results.iter().max_by_key(|r| score(r)) {
+        println!("Max:   {} (seed {:04})", score(max), max.seed);
+    }
+}
+
+/// `results/{日時}.csv` に保存する (同じ秒に実行した結果があれば `{日時}-2.csv` などにする)
+pub fn save_csv(results: &[SeedResult]) -> Result<PathBuf, String> {
+    fs::create_dir_all(RESULTS_DIR).map_err(|_e| ErrorMessages::FailedCreateDir)?;
+    let name = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let path = (1..)
+        .map(|i| match i {
+            1 => Path::new(RESULTS_DIR).join(format!("{}.csv", name)),
+            i => Path::new(RESULTS_DIR).join(format!("{}-{}.csv", name, i)),
+        })
+        .find(|path| !path.exists())
+        .unwrap();
+    let content = "seed,status,score,time_ms\n".to_string()
+        + &results
+            .iter()
+            .map(|r| {
+                format!(
+                    "{},{},{},{}\n",
+                    r.seed,
+                    r.status.value(),
+                    r.score.map(|s| s.to_string()).unwrap_or_default(),
+                    r.time_ms
+                )
+            })
+            .join("");
+    fs::write(&path, content).map_err(|_e| ErrorMessages::FailedWrite)?;
+    Ok(path)
+}
+
+/// `save_csv` で保存した結果を読み込む
+pub fn load_csv(path: &Path) -> Result<Vec<SeedResult>, String> {
+    let content = fs::read_to_string(path).map_err(|_e| ErrorMessages::FailedGet)?;
+    let invalid = || format!("Invalid {}", path.display());
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields = line.split(',').collect::<Vec<&str>>();
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+            Ok(SeedResult {
+                seed: fields[0].parse().map_err(|_e| invalid())?,
+                status: Status::from_value(fields[1]).ok_or_else(invalid)?,
+                score: match fields[2] {
+                    "" => None,
+                    score => Some(score.parse().map_err(|_e| invalid())?),
+                },
+                time_ms: fields[3].parse().map_err(|_e| invalid())?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,103 @@
+use chrono::TimeZone;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+
+use crate::{config::config, cookie_jar::StoredCookie, http::HttpClient};
+
+/// ログイン中のページには `var userScreenName = "SSlime";` が埋め込まれている (ログインしていなければ空)
+static USER_SCREEN_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"var\s+userScreenName\s*=\s*"([^"]*)""#).unwrap());
+
+/// ログアウトなどの POST に必要な CSRF トークン (`var csrfToken = "...";`)
+static CSRF_TOKEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"var\s+csrfToken\s*=\s*"([^"]*)""#).unwrap());
+
+/// キャッシュを使うと古いセッションの結果が返ってしまうので、毎回リクエストを送る
+async fn fetch_home(client: &HttpClient) -> Result<String, String> {
+    let resp = client
+        .get(&format!("{}/home", config().base_url))
+        .await?
+        .error_for_status()
+        .map_err(|e| e.to_string())?;
+    resp.text().await.map_err(|e| e.to_string())
+}
+
+/// ログイン中のユーザー名 (ログインしていなければ `None`)
+pub async fn fetch_user_name(client: &HttpClient) -> Result<Option<String>, String> {
+    let html = fetch_home(client).await?;
+    let name = USER_SCREEN_NAME_REGEX
+        .captures(&html)
+        .ok_or("userScreenName is not Found in the Page")?[1]
+        .to_string();
+    Ok(Some(name).filter(|name| !name.is_empty()))
+}
+
+/// サーバー側でセッションを無効にする (`/logout` に CSRF トークンをつけて POST する)
+pub async fn invalidate_session(client: &HttpClient) -> Result<(), String> {
+    let html = fetch_home(client).await?;
+    let csrf_token = CSRF_TOKEN_REGEX
+        .captures(&html)
+        .ok_or("csrfToken is not Found in the Page")?[1]
+        .to_string();
+    client
+        .post_form(
+            &format!("{}/logout", config().base_url),
+            HeaderMap::new(),
+            &[("csrf_token", csrf_token)],
+        )
+        .await?
+        .error_for_status()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// ブラウザから書き出した cookie (Netscape 形式の cookies.txt) か `REVEL_SESSION` の値から、
+/// `host` の `REVEL_SESSION` だけを取り出す
+pub fn parse_cookie_export(content: &str, host: &str) -> Result<StoredCookie, String> {
+    let host = host.split(':').next().unwrap_or(host);
+    let is_netscape = content
+        .lines()
+        .any(|line| line.split('\t').count() == 7 && !line.starts_with("# "));
+    if !is_netscape {
+        let value = content.trim();
+        let value = value.strip_prefix("REVEL_SESSION=").unwrap_or(value);
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == ';') {
+            return Err("Invalid REVEL_SESSION Value".into());
+        }
+        return Ok(StoredCookie {
+            name: "REVEL_SESSION".to_string(),
+            value: value.to_string(),
+            domain: host.to_string(),
+            path: "/".to_string(),
+            expires: None,
+        });
+    }
+
+    content
+        .lines()
+        // `#HttpOnly_` がついた行も cookie
+        .map(|line| line.strip_prefix("#HttpOnly_").unwrap_or(line))
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.split('\t').collect::<Vec<&str>>())
+        .filter(|fields| fields.len() == 7)
+        .find(|fields| {
+            let domain = fields[0].trim_start_matches('.');
+            fields[5] == "REVEL_SESSION"
+                && (host == domain || host.ends_with(&format!(".{}", domain)))
+        })
+        .map(|fields| StoredCookie {
+            name: "REVEL_SESSION".to_string(),
+            value: fields[6].trim().to_string(),
+            domain: fields[0].to_string(),
+            path: fields[2].to_string(),
+            // 0 はセッション cookie
+            expires: fields[4]
+                .parse()
+                .ok()
+                .filter(|&expires| expires > 0)
+                .and_then(|expires| chrono::Utc.timestamp_opt(expires, 0).single())
+                .map(|expires| expires.to_rfc3339()),
+        })
+        .ok_or_else(|| format!("REVEL_SESSION of {} is not Found in the Cookie File", host))
+}
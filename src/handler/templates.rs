@@ -9,6 +9,16 @@ pub fn main() {
 }
 "###;
 
+pub const CHILD_FILE_WITH_INPUT_TEMPLATE: &str = r###"
+{{use}}
+
+pub fn main() {
+    input! {
+{{input}}
+    }
+}
+"###;
+
 pub const TEST_FILE_TEMPLATE: &str = r##"
 use std::io::BufRead;
 
@@ -42,3 +52,37 @@ fn sample_{{name}}_{{num}}() {
     test_base("{{name}}", {{num}});
 }
 "###;
+
+pub const TEST_FILE_WITH_CHECKER_TEMPLATE: &str = r##"
+use cli_test_dir::*;
+
+const BIN: &'static str = "./{{name}}";
+const PROBLEM_DIR: &'static str = {{problem_dir}};
+
+fn test_base(name: &str, case: &str) {
+    let testdir = TestDir::new(BIN, "");
+    let input = format!("{}/in/{}.in", PROBLEM_DIR, case);
+    let answer = format!("{}/out/{}.out", PROBLEM_DIR, case);
+    let output = testdir
+        .cmd()
+        .arg(name)
+        .output_with_stdin(std::fs::read_to_string(&input).unwrap())
+        .expect_success();
+    let actual = testdir.path(format!("{}.out", case));
+    std::fs::write(&actual, output.stdout_str()).unwrap();
+    let status = std::process::Command::new(format!("{}/checker", PROBLEM_DIR))
+        .arg(&input)
+        .arg(&actual)
+        .arg(&answer)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Wrong Answer on `{}`", case);
+}
+"##;
+
+pub const TEST_FILE_WITH_CHECKER_CHILD_TEMPLATE: &str = r###"
+#[test]
+fn case_{{ident}}() {
+    test_base("{{name}}", "{{case}}");
+}
+"###;
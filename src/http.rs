@@ -0,0 +1,185 @@
+mod cache;
+
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderValue, COOKIE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+        RETRY_AFTER,
+    },
+    Client, Method, RequestBuilder, Response, StatusCode,
+};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::config::config;
+
+/// すべてのリクエストで共有する、最後にリクエストを送った時刻
+static LAST_REQUEST: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+/// タイムアウト・再試行・リクエスト間隔の制限を備えた HTTP クライアント
+pub struct HttpClient {
+    client: Client,
+    cookie_headers: HeaderMap,
+}
+impl HttpClient {
+    pub fn new(cookie_headers: HeaderMap) -> Self {
+        HttpClient {
+            client: Client::builder()
+                .cookie_store(true)
+                .timeout(config().timeout)
+                .build()
+                .unwrap(),
+            cookie_headers,
+        }
+    }
+
+    pub async fn get(&self, url: &str) -> Result<Response, String> {
+        self.send(|client| client.get(url)).await
+    }
+
+    /// GET して、成功 (2xx) したときだけ本文を返す
+    ///
+    /// 本文はキャッシュに保存し、次回からは `ETag` / `Last-Modified` で再検証する
+    /// `--offline` のときはリクエストを送らずにキャッシュだけを使う
+    pub async fn get_text(&self, url: &str) -> Result<String, String> {
+        let cookie = self
+            .cookie_headers
+            .get(COOKIE)
+            .and_then(|v| v.to_str().ok());
+        let cached = cache::load(url, cookie);
+        if config().offline {
+            return cached
+                .map(|entry| entry.body)
+                .ok_or_else(|| format!("{} is not Cached (offline)", url));
+        }
+
+        let mut conditional_headers = HeaderMap::new();
+        if let Some(meta) = cached.as_ref().map(|entry| &entry.meta) {
+            if let Some(etag) = meta
+                .etag
+                .as_ref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                conditional_headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = meta
+                .last_modified
+                .as_ref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                conditional_headers.insert(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let resp = self
+            .send(|client| client.get(url).headers(conditional_headers.clone()))
+            .await?;
+        if resp.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                return Ok(entry.body);
+            }
+        }
+        let resp = resp.error_for_status().map_err(|e| e.to_string())?;
+        let header_value = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let etag = header_value(ETAG);
+        let last_modified = header_value(LAST_MODIFIED);
+        let body = resp.text().await.map_err(|e| e.to_string())?;
+        cache::save(url, cookie, &body, etag, last_modified);
+        Ok(body)
+    }
+
+    pub async fn post_form<T: Serialize + ?Sized>(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        form: &T,
+    ) -> Result<Response, String> {
+        self.send(|client| client.post(url).headers(headers.clone()).form(form))
+            .await
+    }
+
+    /// 一時的なエラー (タイムアウト・接続エラー・429・5xx) のときは指数的に間隔を空けて再試行する
+    ///
+    /// ログインなどを二重に送らないよう、再試行するのは GET と HEAD だけ
+    async fn send<F>(&self, build: F) -> Result<Response, String>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let retries = match build(&self.client).build() {
+            Ok(req) if req.method() == Method::GET || req.method() == Method::HEAD => {
+                config().retries
+            }
+            _ => 0,
+        };
+        let mut delay = Duration::from_secs(1);
+        let mut attempt = 0;
+        loop {
+            wait_interval().await;
+            let result = build(&self.client)
+                .headers(self.cookie_headers.clone())
+                .send()
+                .await;
+
+            let (error, retry_after) = match result {
+                Ok(resp) if resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = resp
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.trim().parse().ok())
+                        .map(Duration::from_secs);
+                    if attempt >= retries {
+                        return Err(format!(
+                            "Rate Limited by {} (429 Too Many Requests). Wait a while or increase --interval",
+                            resp.url().host_str().unwrap_or("server")
+                        ));
+                    }
+                    eprintln!("Rate Limited by {} (429 Too Many Requests)", resp.url());
+                    (format!("{} {}", resp.status(), resp.url()), retry_after)
+                }
+                Ok(resp) if resp.status().is_server_error() => {
+                    if attempt >= retries {
+                        return Ok(resp);
+                    }
+                    (format!("{} {}", resp.status(), resp.url()), None)
+                }
+                Ok(resp) => return Ok(resp),
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < retries => {
+                    (e.to_string(), None)
+                }
+                Err(e) => return Err(e.to_string()),
+            };
+
+            let wait = retry_after.unwrap_or(delay);
+            eprintln!(
+                "Request Failed ({}), retry in {:?} ({}/{})",
+                error,
+                wait,
+                attempt + 1,
+                retries
+            );
+            tokio::time::delay_for(wait).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+/// 前回のリクエストから `config().interval` 以上空くまで待つ
+async fn wait_interval() {
+    let mut last_request = LAST_REQUEST.lock().await;
+    if let Some(last) = *last_request {
+        let elapsed = last.elapsed();
+        if elapsed < config().interval {
+            tokio::time::delay_for(config().interval - elapsed).await;
+        }
+    }
+    *last_request = Some(Instant::now());
+}
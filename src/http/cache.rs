@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::data_dir;
+
+/// `~/.atcoder-create-contest-dir/cache` に URL と送った cookie ごとに保存した HTML
+pub struct CacheEntry {
+    pub body: String,
+    pub meta: CacheMeta,
+}
+
+/// 再検証 (`If-None-Match` / `If-Modified-Since`) に使うヘッダーの値
+#[derive(Serialize, Deserialize, Default)]
+pub struct CacheMeta {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// ログインしているかどうかで中身が変わるので、`Cookie` ヘッダーの値が違えば別のキーにする
+fn cache_path(url: &str, cookie: Option<&str>) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    if let Some(cookie) = cookie {
+        hasher.update(b"\n");
+        hasher.update(cookie.as_bytes());
+    }
+    let key = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    data_dir().join("cache").join(key)
+}
+
+pub fn load(url: &str, cookie: Option<&str>) -> Option<CacheEntry> {
+    let path = cache_path(url, cookie);
+    let body = std::fs::read_to_string(path.with_extension("html")).ok()?;
+    let meta = std::fs::read_to_string(path.with_extension("toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<CacheMeta>(&content).ok())
+        .filter(|meta| meta.url == url)?;
+    Some(CacheEntry { body, meta })
+}
+
+pub fn save(
+    url: &str,
+    cookie: Option<&str>,
+    body: &str,
+    etag: Option<String>,
+    last_modified: Option<String>,
+) {
+    let path = cache_path(url, cookie);
+    let meta = CacheMeta {
+        url: url.to_string(),
+        etag,
+        last_modified,
+    };
+    // キャッシュは保存できなくても本来の処理には影響しないので、エラーは無視する
+    let _ = std::fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| std::fs::write(path.with_extension("html"), body))
+        .and_then(|_| std::fs::write(path.with_extension("toml"), toml::to_string(&meta).unwrap()));
+}
@@ -1,11 +1,18 @@
+mod config;
+mod cookie_jar;
 mod handler;
+mod http;
+mod metadata;
 mod parser;
 mod utils;
 
 use std::fmt;
 
 use crate::{
-    handler::{add_test, create_contest_dir, login},
+    handler::{
+        add_test, batch_create_contest_dir, compare, create_contest_dir, library_checker, login,
+        logout, score, whoami,
+    },
     parser::{parse_arg, ParsedArg},
 };
 
@@ -44,16 +51,28 @@ pub struct ContestInfo {
     name: String,
     kind: Contests,
     url: Option<String>,
+    wait: bool,
+    /// AHC の `tools/` にするローカルテスターの zip もしくはディレクトリ
+    tools: Option<String>,
 }
 
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
+#[derive(Clone)]
 enum Contests {
     ABC,
     H_ABC,
     S_ABC,
     ARC,
     AGC,
+    /// AtCoder Heuristic Contest (解答は 1 つで、サンプルの代わりにローカルテスターを使う)
+    AHC,
+    /// Codeforces (問題の数はコンテストの問題の一覧から決める)
+    CF,
+    /// yukicoder (問題の数はコンテストの問題の一覧から決める)
+    YUKI,
+    /// Library Checker (ローカルの library-checker-problems の問題 1 つ)
+    LC,
 }
 #[allow(dead_code)]
 impl Contests {
@@ -64,6 +83,24 @@ impl Contests {
             Contests::S_ABC => "abc",
             Contests::ARC => "arc",
             Contests::AGC => "agc",
+            Contests::AHC => "ahc",
+            Contests::CF => "cf",
+            Contests::YUKI => "yuki",
+            Contests::LC => "lc",
+        }
+    }
+
+    fn typename<'a>(&self) -> &'a str {
+        match *self {
+            Contests::ABC => "abc",
+            Contests::H_ABC => "h-abc",
+            Contests::S_ABC => "s-abc",
+            Contests::ARC => "arc",
+            Contests::AGC => "agc",
+            Contests::AHC => "ahc",
+            Contests::CF => "cf",
+            Contests::YUKI => "yuki",
+            Contests::LC => "lc",
         }
     }
 
@@ -73,8 +110,11 @@ impl Contests {
         }
         match *self {
             Contests::ABC => create_a_to_x(8),
-            Contests::H_ABC | Contests::ARC | Contests::AGC => create_a_to_x(6),
+            Contests::H_ABC | Contests::ARC | Contests::AGC | Contests::CF | Contests::YUKI => {
+                create_a_to_x(6)
+            }
             Contests::S_ABC => create_a_to_x(4),
+            Contests::AHC | Contests::LC => create_a_to_x(1),
         }
     }
 
@@ -88,6 +128,10 @@ impl Contests {
             "s-abc" => Contests::S_ABC,
             "arc" => Contests::ARC,
             "agc" => Contests::AGC,
+            "ahc" => Contests::AHC,
+            "cf" => Contests::CF,
+            "yuki" => Contests::YUKI,
+            "lc" => Contests::LC,
             _ => None?,
         }
         .into()
@@ -95,7 +139,7 @@ impl Contests {
 }
 impl From<(&str, &str)> for Contests {
     fn from((kind, num): (&str, &str)) -> Self {
-        assert!(kind == "abc" || kind == "arc" || kind == "agc");
+        assert!(kind == "abc" || kind == "arc" || kind == "agc" || kind == "ahc");
         let num: u32 = num.parse().unwrap();
         // -ABC125 4問 ABC126-ABC211 6問 abc212- 8問
         match kind {
@@ -104,6 +148,7 @@ impl From<(&str, &str)> for Contests {
             "abc" => Contests::ABC,
             "arc" => Contests::ARC,
             "agc" => Contests::AGC,
+            "ahc" => Contests::AHC,
             _ => Contests::AGC,
         }
     }
@@ -115,7 +160,15 @@ async fn main() {
 
     match parsed_arg {
         ParsedArg::CreateDir(contest_info) => create_contest_dir(contest_info).await,
-        ParsedArg::Login(user_name, password) => login(user_name, password).await,
-        ParsedArg::AddTest(url, problem_names) => add_test(url, problem_names).await,
+        ParsedArg::Login(login_info) => login(login_info).await,
+        ParsedArg::Logout => logout().await,
+        ParsedArg::Whoami => whoami().await,
+        ParsedArg::AddTest(add_test_info) => add_test(add_test_info).await,
+        ParsedArg::Score(score_info) => score(score_info).await,
+        ParsedArg::Compare(previous) => compare(previous).await,
+        ParsedArg::LibraryChecker(info) => library_checker(info).await,
+        ParsedArg::Batch(contest_infos, jobs) => {
+            batch_create_contest_dir(contest_infos, jobs).await
+        }
     }
 }
@@ -0,0 +1,71 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ErrorMessages;
+
+pub const METADATA_FILE_NAME: &str = "contest.toml";
+
+/// 生成したディレクトリの `contest.toml` に保存するコンテストの情報
+#[derive(Serialize, Deserialize)]
+pub struct ContestMetadata {
+    pub contest_id: String,
+    pub url: Option<String>,
+    pub kind: String,
+    /// AHC でスコアが小さいほど良い問題なら `true`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub minimize: bool,
+    #[serde(default)]
+    pub problems: Vec<ProblemMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProblemMetadata {
+    pub label: String,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+    #[serde(default)]
+    pub sample_count: usize,
+}
+impl ProblemMetadata {
+    pub fn new(label: impl Into<String>) -> Self {
+        ProblemMetadata {
+            label: label.into(),
+            title: None,
+            url: None,
+            time_limit_ms: None,
+            memory_limit_mb: None,
+            sample_count: 0,
+        }
+    }
+}
+
+impl ContestMetadata {
+    pub fn problem(&self, label: &str) -> Option<&ProblemMetadata> {
+        self.problems.iter().find(|p| p.label == label)
+    }
+
+    pub fn problem_mut(&mut self, label: &str) -> Option<&mut ProblemMetadata> {
+        self.problems.iter_mut().find(|p| p.label == label)
+    }
+
+    /// `dir_name` 直下の `contest.toml` を読み込む (存在しなければ `Ok(None)`)
+    pub fn load(dir_name: impl AsRef<Path>) -> Result<Option<ContestMetadata>, String> {
+        let path = dir_name.as_ref().join(METADATA_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path).map_err(|_e| ErrorMessages::FailedGet)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Invalid {}: {}", METADATA_FILE_NAME, e))
+    }
+
+    pub fn save(&self, dir_name: impl AsRef<Path>) -> Result<(), String> {
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(dir_name.as_ref().join(METADATA_FILE_NAME), content)
+            .map_err(|_e| ErrorMessages::FailedWrite.into())
+    }
+}
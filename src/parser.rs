@@ -1,4 +1,7 @@
-use std::io::{stdin, stdout, Write};
+use std::{
+    io::{stdin, stdout, Write},
+    time::Duration,
+};
 
 use clap::{
     self, crate_authors, crate_description, crate_name, crate_version, App, Arg, ArgMatches,
@@ -6,13 +9,21 @@ use clap::{
 };
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
 
-use crate::{ContestInfo, Contests};
+use crate::{
+    config::{config, init_config, Config, Lang, PASSWORD_ENV, USERNAME_ENV},
+    cookie_jar::{read_passphrase, PASSPHRASE_ENV},
+    handler::judge::judge_of_url,
+    ContestInfo, Contests,
+};
 
 struct OptionalContestInfo {
     name: Option<String>,
     kind: Option<Contests>,
     url: Option<String>,
+    wait: bool,
+    tools: Option<String>,
 }
 impl From<OptionalContestInfo> for Result<ContestInfo, ()> {
     fn from(info: OptionalContestInfo) -> Result<ContestInfo, ()> {
@@ -21,6 +32,8 @@ impl From<OptionalContestInfo> for Result<ContestInfo, ()> {
                 name,
                 kind,
                 url: info.url,
+                wait: info.wait,
+                tools: info.tools,
             })
         } else {
             Err(())
@@ -28,53 +41,235 @@ impl From<OptionalContestInfo> for Result<ContestInfo, ()> {
     }
 }
 
+pub struct AddTestInfo {
+    pub url: Option<String>,
+    pub kind: Option<Contests>,
+    pub problem: Option<String>,
+}
+
+pub enum LoginMethod {
+    /// ユーザー名とパスワード
+    Password(String, String),
+    /// ブラウザから書き出した cookie のファイル
+    CookieFile(String),
+}
+
+pub struct LoginInfo {
+    pub method: LoginMethod,
+    /// cookie を暗号化して保存するときのパスフレーズ
+    pub passphrase: Option<String>,
+}
+
+/// `score` の引数
+pub struct ScoreInfo {
+    /// 実行するシード (`None` なら `in/` にあるすべての入力)
+    pub seeds: Option<Vec<u32>>,
+    pub jobs: usize,
+    /// 1 つのシードの実行時間の上限 (`None` なら問題の実行時間制限)
+    pub time_limit: Option<Duration>,
+}
+
+/// `library-checker` の引数
+pub struct LibraryCheckerInfo {
+    /// 問題の名前 (`unionfind` など)
+    pub name: String,
+    /// library-checker-problems のパス
+    pub repo: String,
+}
+
 pub enum ParsedArg {
     CreateDir(ContestInfo),
-    Login(String, String),
-    AddTest(String, Vec<String>),
+    Login(LoginInfo),
+    Logout,
+    Whoami,
+    AddTest(AddTestInfo),
+    Batch(Vec<ContestInfo>, usize),
+    Score(ScoreInfo),
+    /// `--previous` なら前回の結果と、そうでなければ最高スコアと比べる
+    Compare(bool),
+    LibraryChecker(LibraryCheckerInfo),
 }
 pub fn parse_arg() -> Result<ParsedArg, String> {
     let app = create_app();
     let matches = app.get_matches();
 
+    init_config(parse_config_arg(&matches)?);
+
     if let Some(matches) = matches.subcommand_matches("login") {
-        parse_login_arg(matches).map(|res| ParsedArg::Login(res.0, res.1))
+        parse_login_arg(matches).map(ParsedArg::Login)
+    } else if matches.subcommand_matches("logout").is_some() {
+        Ok(ParsedArg::Logout)
+    } else if matches.subcommand_matches("whoami").is_some() {
+        Ok(ParsedArg::Whoami)
     } else if let Some(matches) = matches.subcommand_matches("add_test") {
-        parse_add_test_arg(matches).map(|res| ParsedArg::AddTest(res.0, res.1))
+        parse_add_test_arg(matches).map(ParsedArg::AddTest)
+    } else if let Some(matches) = matches.subcommand_matches("batch") {
+        parse_batch_arg(matches).map(|res| ParsedArg::Batch(res.0, res.1))
+    } else if let Some(matches) = matches.subcommand_matches("score") {
+        parse_score_arg(matches).map(ParsedArg::Score)
+    } else if let Some(matches) = matches.subcommand_matches("compare") {
+        Ok(ParsedArg::Compare(matches.is_present("previous")))
+    } else if let Some(matches) = matches.subcommand_matches("library-checker") {
+        Ok(ParsedArg::LibraryChecker(LibraryCheckerInfo {
+            name: matches.value_of("name").unwrap().to_string(),
+            repo: matches.value_of("repo").unwrap().to_string(),
+        }))
     } else {
         parse_default_arg(&matches).map(ParsedArg::CreateDir)
     }
 }
 
-fn parse_login_arg(matches: &ArgMatches) -> Result<(String, String), String> {
-    let user_name = if let Some(u) = matches.value_of("user_name") {
-        u.to_string()
+/// サブコマンドに共通の引数 (サブコマンドの後ろに書かれたものを優先する)
+fn parse_config_arg(matches: &ArgMatches) -> Result<Config, String> {
+    let sub_matches = matches.subcommand().1;
+    let value_of = |name: &str| {
+        sub_matches
+            .and_then(|m| m.value_of(name))
+            .or_else(|| matches.value_of(name))
+    };
+
+    let mut config = Config::load(value_of("profile"))?;
+    if let Some(v_fetch_jobs) = value_of("fetch_jobs") {
+        config.fetch_jobs = v_fetch_jobs.parse().map_err(|_e| "Invalid Fetch Jobs !")?;
+    }
+    if let Some(v_timeout) = value_of("timeout") {
+        let timeout: f64 = v_timeout.parse().map_err(|_e| "Invalid Timeout !")?;
+        if timeout.is_nan() || timeout <= 0.0 {
+            return Err("Invalid Timeout !".into());
+        }
+        config.timeout = Duration::from_secs_f64(timeout);
+    }
+    if let Some(v_retries) = value_of("retries") {
+        config.retries = v_retries.parse().map_err(|_e| "Invalid Retries !")?;
+    }
+    if let Some(v_interval) = value_of("interval") {
+        let interval: f64 = v_interval.parse().map_err(|_e| "Invalid Interval !")?;
+        if interval.is_nan() || interval < 0.0 {
+            return Err("Invalid Interval !".into());
+        }
+        config.interval = Duration::from_secs_f64(interval);
+    }
+    if let Some(v_lang) = value_of("lang") {
+        config.lang = Lang::from_name(v_lang).ok_or("Invalid Lang !")?;
+    }
+    config.offline =
+        matches.is_present("offline") || sub_matches.is_some_and(|m| m.is_present("offline"));
+    Ok(config)
+}
+
+fn parse_login_arg(matches: &ArgMatches) -> Result<LoginInfo, String> {
+    let passphrase = if matches.is_present("encrypt") {
+        Some(read_new_passphrase()?)
     } else {
-        print!("user name: ");
-        stdout().flush().unwrap();
+        None
+    };
+    if let Some(path) = matches.value_of("cookie_file") {
+        return Ok(LoginInfo {
+            method: LoginMethod::CookieFile(path.to_string()),
+            passphrase,
+        });
+    }
+
+    let credentials = match matches.value_of("credentials") {
+        Some(path) => Some(read_credentials(path)?),
+        None => None,
+    };
+    let user_name = matches
+        .value_of("user_name")
+        .map(|u| u.to_string())
+        .or_else(|| credentials.as_ref().and_then(|c| c.username.clone()))
+        .or_else(|| std::env::var(USERNAME_ENV).ok());
+    let user_name = match user_name {
+        Some(user_name) => user_name,
+        None if matches.is_present("password_stdin") => {
+            return Err("--user is Required with --password-stdin !".into())
+        }
+        None => {
+            print!("user name: ");
+            stdout().flush().unwrap();
+
+            let mut name = String::new();
+            stdin().read_line(&mut name).unwrap();
+            name.trim().to_string()
+        }
+    };
 
-        let mut name = String::new();
-        stdin().read_line(&mut name).unwrap();
-        name.trim().to_string()
+    // --password-stdin > --credentials > 環境変数 > 端末での入力 の順に使う
+    let password = if matches.is_present("password_stdin") {
+        let mut password = String::new();
+        stdin()
+            .read_line(&mut password)
+            .map_err(|e| e.to_string())?;
+        password.trim_end_matches(&['\r', '\n'][..]).to_string()
+    } else if let Some(credentials) = credentials {
+        credentials.password
+    } else if let Ok(password) = std::env::var(PASSWORD_ENV) {
+        password
+    } else {
+        rpassword::read_password_from_tty(Some("password: ")).map_err(|e| e.to_string())?
     };
-    let password =
-        rpassword::read_password_from_tty(Some("password: ")).map_err(|e| e.to_string())?;
-    Ok((user_name, password))
+    if password.is_empty() {
+        return Err("Password is Empty !".into());
+    }
+    Ok(LoginInfo {
+        method: LoginMethod::Password(user_name, password),
+        passphrase,
+    })
 }
 
-fn parse_add_test_arg(matches: &ArgMatches) -> Result<(String, Vec<String>), String> {
+/// `--credentials` で指定するファイル
+#[derive(Deserialize)]
+struct Credentials {
+    username: Option<String>,
+    password: String,
+}
+
+/// パスワードを書いたファイルなので、本人以外が読めるパーミッションなら読み込まない
+fn read_credentials(path: &str) -> Result<Credentials, String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to Read {}: {}", path, e))?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            return Err(format!(
+                "Permissions {:o} of {} are too Open (run `chmod 600 {}`)",
+                mode & 0o777,
+                path,
+                path
+            ));
+        }
+    }
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to Read {}: {}", path, e))?;
+    toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path, e))
+}
+
+/// cookie を暗号化するパスフレーズ (端末で入力する場合は確認のために 2 回入力する)
+fn read_new_passphrase() -> Result<String, String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let passphrase = read_passphrase("cookie passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err("Passphrase is Empty !".into());
+    }
+    if read_passphrase("confirm passphrase: ")? != passphrase {
+        return Err("Passphrases do not Match !".into());
+    }
+    Ok(passphrase)
+}
+
+fn parse_add_test_arg(matches: &ArgMatches) -> Result<AddTestInfo, String> {
     let mut url = None;
     let mut kind: Option<Contests> = None;
     if let Some(v_url) = matches.value_of("url") {
-        let extracted_name = extract_name_from_url(v_url).map_err(|_e| "Invalid URL !")?;
-        url = Some(format!("https://atcoder.jp/contests/{}", extracted_name));
-        let formatted_name = format_contest_name(&extracted_name);
-        match formatted_name {
-            ContestKind::AXC(v_kind, v_num) => {
-                kind = Some((v_kind.as_str(), v_num.as_str()).into());
-            }
-            ContestKind::Other(_name) => (),
-        }
+        let (contest_url, _name, url_kind) = parse_contest_url(v_url).ok_or("Invalid URL !")?;
+        url = Some(contest_url);
+        kind = url_kind;
     }
 
     if let Some(v_type) = matches.value_of("type") {
@@ -84,15 +279,148 @@ fn parse_add_test_arg(matches: &ArgMatches) -> Result<(String, Vec<String>), Str
             "s-abc" => Contests::S_ABC,
             "arc" => Contests::ARC,
             "agc" => Contests::AGC,
+            "ahc" => Contests::AHC,
+            "cf" => Contests::CF,
+            "yuki" => Contests::YUKI,
             _ => return Err("invalid kind !".to_string()),
         });
     }
 
-    if let (Some(url), Some(kind)) = (url, kind) {
-        Ok((url, kind.problem_names()))
+    let problem = matches.value_of("problem").map(|x| x.to_lowercase());
+
+    Ok(AddTestInfo { url, kind, problem })
+}
+
+fn parse_batch_arg(matches: &ArgMatches) -> Result<(Vec<ContestInfo>, usize), String> {
+    let mut urls = Vec::new();
+    if let Some(v_range) = matches.value_of("range") {
+        urls.extend(
+            expand_contest_range(v_range)?
+                .into_iter()
+                .map(|name| format!("{}/contests/{}", config().base_url, name)),
+        );
+    }
+    if let Some(v_file) = matches.value_of("file") {
+        let content = std::fs::read_to_string(v_file).map_err(|e| e.to_string())?;
+        urls.extend(
+            content
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| line.to_string()),
+        );
+    }
+    if urls.is_empty() {
+        return Err("Range or File is Required !".into());
+    }
+
+    let kind = match matches.value_of("type") {
+        Some(v_type) => {
+            Some(Contests::from_typename(v_type.to_lowercase()).ok_or("Invalid Type !")?)
+        }
+        None => None,
+    };
+    let jobs = match matches.value_of("jobs") {
+        Some(v_jobs) => v_jobs.parse().map_err(|_e| "Invalid Jobs !")?,
+        None => 4,
+    };
+
+    let contest_infos = urls
+        .iter()
+        .map(|url| {
+            let (contest_url, name, extracted_kind) =
+                parse_contest_url(url).ok_or(format!("Invalid URL ! ({})", url))?;
+            let kind = kind
+                .clone()
+                .or(extracted_kind)
+                .ok_or(format!("Kind is Required ! ({})", url))?;
+            Ok(ContestInfo {
+                name,
+                kind,
+                url: Some(contest_url),
+                wait: false,
+                tools: None,
+            })
+        })
+        .collect::<Result<Vec<ContestInfo>, String>>()?;
+    Ok((contest_infos, jobs))
+}
+
+fn parse_score_arg(matches: &ArgMatches) -> Result<ScoreInfo, String> {
+    let seeds = match matches.value_of("seeds") {
+        Some(v_seeds) => Some(expand_seed_range(v_seeds)?),
+        None => None,
+    };
+    let jobs = match matches.value_of("jobs") {
+        Some(v_jobs) => v_jobs.parse().map_err(|_e| "Invalid Jobs !")?,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let time_limit = match matches.value_of("time_limit") {
+        Some(v_time_limit) => {
+            let time_limit: f64 = v_time_limit.parse().map_err(|_e| "Invalid Time Limit !")?;
+            if time_limit.is_nan() || time_limit <= 0.0 {
+                return Err("Invalid Time Limit !".into());
+            }
+            Some(Duration::from_secs_f64(time_limit))
+        }
+        None => None,
+    };
+    Ok(ScoreInfo {
+        seeds,
+        jobs,
+        time_limit,
+    })
+}
+
+static SEED_RANGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([0-9]+)(?:\.\.(=)?([0-9]+))?$").unwrap());
+/// `0..100` を 0, 1, ..., 99 に展開する (`0..=99` は終端を含み、`5` は 5 だけ)
+fn expand_seed_range(range: &str) -> Result<Vec<u32>, String> {
+    let c = SEED_RANGE_REGEX.captures(range).ok_or("Invalid Seeds !")?;
+    let start: u32 = c[1].parse().map_err(|_e| "Invalid Seeds !")?;
+    let end: u32 = match c.get(3) {
+        Some(end) => end.as_str().parse().map_err(|_e| "Invalid Seeds !")?,
+        None => return Ok(vec![start]),
+    };
+    let seeds = if c.get(2).is_some() {
+        (start..=end).collect::<Vec<u32>>()
+    } else {
+        (start..end).collect()
+    };
+    if seeds.is_empty() {
+        return Err("Invalid Seeds !".into());
+    }
+    Ok(seeds)
+}
+
+static RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^([a-z][a-z_-]*?)([0-9]+)\.\.(=)?([a-z][a-z_-]*?)?([0-9]+)$").unwrap()
+});
+/// `abc300..abc320` や `abc300..=320` を `abc300`, `abc301`, ... に展開する
+/// (シードと同じく `..` は終端を含まず、`..=` は終端を含む)
+fn expand_contest_range(range: &str) -> Result<Vec<String>, String> {
+    let c = RANGE_REGEX.captures(range).ok_or("Invalid Range !")?;
+    let prefix = c[1].to_lowercase();
+    if let Some(end_prefix) = c.get(4) {
+        if end_prefix.as_str().to_lowercase() != prefix {
+            return Err("Invalid Range !".into());
+        }
+    }
+    let width = c[2].len();
+    let start: u32 = c[2].parse().map_err(|_e| "Invalid Range !")?;
+    let end: u32 = c[5].parse().map_err(|_e| "Invalid Range !")?;
+    let nums = if c.get(3).is_some() {
+        (start..=end).collect::<Vec<u32>>()
     } else {
-        Err("Invalid Args".to_string())
+        (start..end).collect()
+    };
+    if nums.is_empty() {
+        return Err("Invalid Range !".into());
     }
+    Ok(nums
+        .into_iter()
+        .map(|num| format!("{}{:0width$}", prefix, num, width = width))
+        .collect())
 }
 
 fn parse_default_arg(matches: &ArgMatches) -> Result<ContestInfo, String> {
@@ -100,19 +428,18 @@ fn parse_default_arg(matches: &ArgMatches) -> Result<ContestInfo, String> {
         name: None,
         kind: None,
         url: None,
+        wait: matches.is_present("wait"),
+        tools: matches.value_of("tools").map(|x| x.to_string()),
     };
 
     if let Some(v_url) = matches.value_of("url") {
-        let extracted_name = extract_name_from_url(v_url).map_err(|_e| "Invalid URL !")?;
-        contest_info.url = Some(format!("https://atcoder.jp/contests/{}", extracted_name));
-        let formatted_name = format_contest_name(&extracted_name);
-        match formatted_name {
-            ContestKind::AXC(kind, num) => {
-                contest_info.name = Some(format!("{}-{}", kind, num));
-                contest_info.kind = Some((kind.as_str(), num.as_str()).into());
-            }
-            ContestKind::Other(name) => contest_info.name = Some(name),
+        let (contest_url, name, kind) = parse_contest_url(v_url).ok_or("Invalid URL !")?;
+        if contest_info.wait && !contest_url.starts_with(&format!("{}/", config().base_url)) {
+            return Err("--wait is Only for AtCoder !".into());
         }
+        contest_info.url = Some(contest_url);
+        contest_info.name = Some(name);
+        contest_info.kind = kind;
     }
 
     if let Some(v_name) = matches.value_of("name") {
@@ -131,6 +458,13 @@ fn parse_default_arg(matches: &ArgMatches) -> Result<ContestInfo, String> {
             Some(Contests::from_typename(v_type.to_lowercase()).ok_or("Invalid Type !")?);
     }
 
+    if contest_info.wait && contest_info.url.is_none() {
+        return Err("URL is Required for --wait !".into());
+    }
+    if contest_info.tools.is_some() && !matches!(contest_info.kind, None | Some(Contests::AHC)) {
+        return Err("--tools is Only for AHC !".into());
+    }
+
     let r: Result<ContestInfo, ()> = contest_info.into();
     r.map_err(|_e| "Name and Kind is Required !".into())
 }
@@ -164,6 +498,73 @@ fn create_app<'a>() -> App<'a, 'a> {
                 .value_name("TYPE")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("fetch_jobs")
+                .help("max number of problem pages fetched at the same time (default: 4)")
+                .long("fetch-jobs")
+                .value_name("N")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("timeout")
+                .help("timeout of each request in seconds (default: 10)")
+                .long("timeout")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("retries")
+                .help("number of retries on transient errors (default: 3)")
+                .long("retries")
+                .value_name("N")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("interval")
+                .help("minimum interval between requests in seconds (default: 0.2)")
+                .long("interval")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("lang")
+                .help("language of problem statements: ja, en or auto (default: auto)")
+                .long("lang")
+                .value_name("LANG")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("profile")
+                .help("account profile (cookies and settings in ~/.atcoder-create-contest-dir/profiles/NAME)")
+                .long("profile")
+                .value_name("NAME")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("offline")
+                .help("use only cached pages without sending requests")
+                .long("offline")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("wait")
+                .help("wait for the contest to start and then fetch samples")
+                .short("w")
+                .long("wait"),
+        )
+        .arg(
+            Arg::with_name("tools")
+                .help("local tester zip or directory copied to `tools/` (AHC only, default: the zip linked from the problem)")
+                .long("tools")
+                .value_name("PATH")
+                .takes_value(true),
+        )
         .subcommand(
             SubCommand::with_name("login")
                 .about("login to AtCoder (for contest on going)")
@@ -174,20 +575,54 @@ fn create_app<'a>() -> App<'a, 'a> {
                         .long("user")
                         .value_name("USER_NAME")
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("cookie_file")
+                        .help("import REVEL_SESSION from cookies.txt (Netscape format) or a file with its value (`-` for stdin)")
+                        .long("cookie-file")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .conflicts_with("user_name"),
+                )
+                .arg(
+                    Arg::with_name("password_stdin")
+                        .help("read the password from the first line of stdin")
+                        .long("password-stdin")
+                        .conflicts_with_all(&["cookie_file", "credentials"]),
+                )
+                .arg(
+                    Arg::with_name("credentials")
+                        .help("TOML file with `username` and `password` (must not be readable by others)")
+                        .long("credentials")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .conflicts_with("cookie_file"),
+                )
+                .arg(
+                    Arg::with_name("encrypt")
+                        .help("encrypt the saved cookie with a passphrase (or ATCODER_COOKIE_PASSPHRASE)")
+                        .long("encrypt"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("logout")
+                .about("logout from AtCoder and remove the saved cookie"),
+        )
+        .subcommand(
+            SubCommand::with_name("whoami")
+                .about("show the user name of the saved session and when it expires"),
+        )
         .subcommand(
             SubCommand::with_name("add_test")
                 .about("add test on existing dir")
                 .visible_aliases(&["add-test", "test"])
                 .arg(
                     Arg::with_name("url")
-                        .help("contest url")
+                        .help("contest url (default: url in contest.toml)")
                         .short("u")
                         .long("url")
                         .value_name("URL")
-                        .takes_value(true)
-                        .required(true),
+                        .takes_value(true),
                 )
                 .arg(
                     Arg::with_name("type")
@@ -196,30 +631,145 @@ fn create_app<'a>() -> App<'a, 'a> {
                         .long("type")
                         .value_name("TYPE")
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("problem")
+                        .help("update only this problem's tests (e.g. c)")
+                        .short("p")
+                        .long("problem")
+                        .value_name("PROBLEM")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("batch")
+                .about("create dirs for multiple contests at once")
+                .arg(
+                    Arg::with_name("range")
+                        .help("contest range (e.g. abc300..abc320, use ..= to include the end)")
+                        .value_name("RANGE")
+                        .required_unless("file"),
+                )
+                .arg(
+                    Arg::with_name("file")
+                        .help("file with one contest url per line")
+                        .short("f")
+                        .long("file")
+                        .value_name("FILE")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .help("contest type (shared by all contests)")
+                        .short("t")
+                        .long("type")
+                        .value_name("TYPE")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("jobs")
+                        .help("max number of contests fetched at the same time")
+                        .short("j")
+                        .long("jobs")
+                        .value_name("JOBS")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("score")
+                .about("run the solution on each seed and score it with the local tester (AHC)")
+                .arg(
+                    Arg::with_name("seeds")
+                        .help("seeds to run (e.g. 0..100, default: all inputs in `in/`)")
+                        .short("s")
+                        .long("seeds")
+                        .value_name("SEEDS")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("jobs")
+                        .help("max number of seeds run at the same time (default: number of CPUs)")
+                        .short("j")
+                        .long("jobs")
+                        .value_name("JOBS")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("time_limit")
+                        .help("time limit of each seed in seconds (default: time limit of the problem)")
+                        .long("time-limit")
+                        .value_name("SEC")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("compare")
+                .about("compare the last `score` run with the best scores of each seed (AHC)")
+                .arg(
+                    Arg::with_name("previous")
+                        .help("compare with the run before the last one instead of the best scores")
+                        .short("p")
+                        .long("previous"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("library-checker")
+                .about("create a Library Checker problem from a local library-checker-problems")
+                .arg(
+                    Arg::with_name("name")
+                        .help("problem name (e.g. unionfind)")
+                        .value_name("NAME")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("repo")
+                        .help("path to library-checker-problems")
+                        .long("repo")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true),
                 ),
         );
     app
 }
 
-static URL_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^https?://atcoder.jp/contests/([^/]+).*$").unwrap());
-pub fn extract_name_from_url(url: &str) -> Result<String, ()> {
-    match URL_REGEX.captures(url) {
-        Some(c) => Ok(c[1].to_string()),
-        None => Err(()),
-    }
+/// コンテストの URL、ディレクトリ名、種類 (URL から決まる場合)
+fn parse_contest_url(url: &str) -> Option<(String, String, Option<Contests>)> {
+    let (judge, contest_id) = judge_of_url(url)?;
+    let (name, kind) = judge.contest_dir(&contest_id);
+    Some((judge.contest_url(&contest_id), name, kind))
 }
 
 static AXC_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?i)^(a[bgr]c)[-_]?([0-9]{3})$").unwrap());
+    Lazy::new(|| Regex::new(r"(?i)^(a[bgrh]c)[-_]?([0-9]{3})$").unwrap());
 #[allow(clippy::upper_case_acronyms)]
-enum ContestKind {
+pub enum ContestKind {
     AXC(String, String),
     Other(String),
 }
-fn format_contest_name(name: &str) -> ContestKind {
+pub fn format_contest_name(name: &str) -> ContestKind {
     match AXC_REGEX.captures(name) {
-        Some(c) => ContestKind::AXC((&c[1]).to_lowercase(), (&c[2]).to_string()),
+        Some(c) => ContestKind::AXC(c[1].to_lowercase(), c[2].to_string()),
         None => ContestKind::Other(name.to_lowercase().replace("_", "-")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contest_range_excludes_the_end_like_seeds() {
+        assert_eq!(
+            expand_contest_range("abc098..abc100").unwrap(),
+            ["abc098", "abc099"]
+        );
+        assert_eq!(
+            expand_contest_range("ABC098..=100").unwrap(),
+            ["abc098", "abc099", "abc100"]
+        );
+        assert_eq!(expand_seed_range("98..100").unwrap(), [98, 99]);
+        assert!(expand_contest_range("abc100..abc100").is_err());
+        assert!(expand_contest_range("abc100..arc101").is_err());
+    }
+}
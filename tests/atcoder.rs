@@ -0,0 +1,1006 @@
+mod common;
+
+use std::{io::Write, process::Stdio};
+
+use common::{save_cookie, Env, FixtureResponse, FixtureServer};
+
+#[test]
+fn create_from_url_fetches_samples_and_metadata() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+
+    let output = env.run(&["-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+
+    assert_eq!(env.read("abc-100/tests/a/a_1.input"), "5 4\n");
+    assert_eq!(env.read("abc-100/tests/a/a_3.output"), ":(\n");
+    assert_eq!(env.read("abc-100/tests/c/c_1.input"), "3\n5 2 4\n");
+    assert!(env.read("abc-100/tests/d.rs").contains("fn sample_d_2()"));
+    assert!(!env.path("abc-100/tests/d/d_3.input").exists());
+
+    let metadata = env.read("abc-100/contest.toml");
+    assert!(metadata.contains("contest_id = 'abc100'"));
+    assert!(metadata.contains("kind = 's-abc'"));
+    assert!(metadata.contains("title = 'Happy Birthday!'"));
+    assert!(metadata.contains(&format!(
+        "url = '{}'",
+        server.url("/contests/abc100/tasks/abc100_a")
+    )));
+    assert!(metadata.contains("time_limit_ms = 2000"));
+    assert!(metadata.contains("memory_limit_mb = 976"));
+
+    assert!(env
+        .read("abc-100/src/a.rs")
+        .contains("    input! {\n        a: usize,\n        b: usize,\n    }"));
+    assert!(env
+        .read("abc-100/src/c.rs")
+        .contains("        n: usize,\n        a: [usize; n],\n"));
+    assert!(env
+        .read("abc-100/src/d.rs")
+        .contains("        m: usize,\n        xyz: [(i64, i64, i64); n],\n"));
+
+    assert!(env.read("abc-100/src/a.rs").starts_with(&format!(
+        "//! A - Happy Birthday!
+//!
+//! <{}>
+//!
+//! Time Limit: 2000 ms / Memory Limit: 976 MB
+//!
+//! # 制約
+//!
+//! - $A, B$ は $1$ 以上 $16$ 以下の整数
+//! - $A+B$ は $16$ 以下である.
+
+use proconio::input;
+",
+        server.url("/contests/abc100/tasks/abc100_a")
+    )));
+
+    let statement = env.read("abc-100/statements/a.md");
+    assert!(statement.starts_with("# A - Happy Birthday!\n"));
+    assert!(statement.contains("Time Limit: 2000 ms / Memory Limit: 976 MB"));
+    assert!(statement.contains("## 制約\n\n- $A, B$ は $1$ 以上 $16$ 以下の整数\n- $A+B$ は"));
+    assert!(statement.contains("## 入力例 1\n\n```\n5 4\n```"));
+    assert!(statement.contains("それぞれ 5 切れと 4 切れ食べることができる."));
+}
+
+#[test]
+fn stored_cookie_is_sent_with_requests() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=fixture-session");
+
+    let output = env.run(&["-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+
+    let requests = server.requests();
+    assert!(!requests.is_empty());
+    assert!(requests
+        .iter()
+        .all(|r| r.header("cookie") == Some("REVEL_SESSION=fixture-session")));
+}
+
+#[test]
+fn failed_problem_does_not_abort_other_problems() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/contests/abc100/tasks/abc100_b",
+        FixtureResponse::status(503),
+    );
+    let env = Env::new(&server);
+
+    let output = env.run(&["-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to Fetch Samples of `b`"));
+
+    assert_eq!(env.read("abc-100/tests/a/a_1.input"), "5 4\n");
+    assert!(!env.path("abc-100/tests/b/b_1.input").exists());
+    assert_eq!(env.read("abc-100/tests/c/c_1.output"), "3\n");
+}
+
+#[test]
+fn add_test_problem_updates_only_that_problem() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    assert!(env.run(&["-n", "abc100"]).status.success());
+    assert!(env
+        .run_in(
+            env.path("abc-100"),
+            &["add_test", "-u", &server.url("/contests/abc100")]
+        )
+        .status
+        .success());
+
+    std::fs::write(env.path("abc-100/tests/c/c_2.output"), "1\n").unwrap();
+    std::fs::remove_file(env.path("abc-100/tests/c/c_3.input")).unwrap();
+    std::fs::write(env.path("abc-100/tests/a/a_1.output"), "local\n").unwrap();
+
+    let output = env.run_in(env.path("abc-100"), &["add_test", "--problem", "c"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sample c_2: changed"));
+    assert!(stdout.contains("sample c_3: added"));
+    assert!(!stdout.contains("sample c_1"));
+
+    assert_eq!(env.read("abc-100/tests/c/c_2.output"), "0\n");
+    assert_eq!(env.read("abc-100/tests/a/a_1.output"), "local\n");
+}
+
+#[test]
+fn wait_fetches_samples_after_start_time() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+
+    let output = env.run(&["--wait", "-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Contest starts at 2018-06-16 21:00:00 +09:00"));
+    assert_eq!(env.read("abc-100/tests/b/b_2.output"), "1100\n");
+}
+
+#[test]
+fn wait_retries_task_pages_that_are_not_open_yet() {
+    let server = FixtureServer::start("atcoder");
+    let path = "/contests/abc100/tasks/abc100_b";
+    server.route("GET", path, FixtureResponse::status(404));
+    let env = Env::new(&server);
+
+    let child = env
+        .command()
+        .args(["--wait", "-u", &server.url("/contests/abc100")])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // 最初の取得が 404 になってから問題ページを公開する
+    while !server.requests().iter().any(|r| r.path == path) {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    let page = std::fs::read_to_string(
+        common::fixtures_dir().join("atcoder/contests/abc100/tasks/abc100_b.html"),
+    )
+    .unwrap();
+    server.route("GET", path, FixtureResponse::ok(page));
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to Fetch Samples of `b`"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("retry in"));
+    assert_eq!(env.read("abc-100/tests/b/b_2.output"), "1100\n");
+    assert_eq!(env.read("abc-100/tests/a/a_1.input"), "5 4\n");
+}
+
+#[test]
+fn cached_pages_are_revalidated_and_used_offline() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    let url = server.url("/contests/abc100");
+    assert!(env.run(&["-u", &url]).status.success());
+
+    std::fs::rename(env.path("abc-100"), env.path("abc-100-first")).unwrap();
+    assert!(env.run(&["-u", &url]).status.success());
+    let revalidated = server
+        .requests()
+        .into_iter()
+        .filter(|r| r.header("if-none-match").is_some())
+        .count();
+    assert_eq!(revalidated, 5);
+
+    std::fs::rename(env.path("abc-100"), env.path("abc-100-second")).unwrap();
+    let sent = server.requests().len();
+    assert!(env.run(&["--offline", "-u", &url]).status.success());
+    assert_eq!(server.requests().len(), sent);
+    assert_eq!(env.read("abc-100/tests/c/c_1.input"), "3\n5 2 4\n");
+
+    let output = env.run(&["--offline", "-u", &server.url("/contests/abc101")]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not Cached (offline)"));
+
+    // ログインしていないときのキャッシュはログインしているときには使わない
+    save_cookie(&env, "REVEL_SESSION=logged-in");
+    std::fs::rename(env.path("abc-100"), env.path("abc-100-third")).unwrap();
+    let output = env.run(&["--offline", "-u", &url]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not Cached (offline)"));
+}
+
+#[test]
+fn samples_are_matched_by_number_in_any_layout() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+
+    let output = env.run(&["-u", &server.url("/contests/tricky"), "-t", "s-abc"]);
+    assert!(output.status.success());
+
+    assert_eq!(env.read("tricky/tests/a/a_1.input"), "1\n");
+    assert_eq!(env.read("tricky/tests/a/a_1.output"), "one\n");
+    assert_eq!(env.read("tricky/tests/a/a_2.input"), "2\n");
+    assert_eq!(env.read("tricky/tests/a/a_2.output"), "two\n");
+    assert!(!env.path("tricky/tests/a/a_3.input").exists());
+
+    assert_eq!(env.read("tricky/tests/b/b_1.output"), "1 1\n");
+    assert_eq!(env.read("tricky/tests/b/b_2.output"), "2 2\n");
+    assert!(!env.path("tricky/tests/b/b_3.input").exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning on Samples of `b`: Sample Input 3 has no Sample Output"));
+
+    assert_eq!(env.read("tricky/tests/c/c_1.output"), "2\n");
+
+    assert!(env.read("tricky/src/b.rs").ends_with(
+        r#"
+use proconio::{input, marker::Chars};
+
+pub fn main() {
+    input! {
+        h: usize,
+        w: usize,
+        s: String,
+        c: [Chars; h],
+        a: [[i64; w]; h],
+        q: usize,
+        lr: [(usize, usize); q],
+        p: [f64; 2 * q - 1],
+    }
+}
+"#
+    ));
+    // 入力形式がない問題はテンプレートのまま
+    assert!(env.read("tricky/src/a.rs").contains("        n: usize,\n"));
+
+    let metadata = env.read("tricky/contest.toml");
+    assert!(metadata.contains("time_limit_ms = 2500"));
+    assert!(metadata.contains("memory_limit_mb = 256"));
+}
+
+#[test]
+fn lang_option_selects_statement_language() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+
+    let output = env.run(&["--lang", "en", "-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+    let paths = server
+        .requests()
+        .into_iter()
+        .map(|r| r.path)
+        .collect::<Vec<String>>();
+    assert!(paths.contains(&"/contests/abc100/tasks?lang=en".to_string()));
+    assert!(paths.contains(&"/contests/abc100/tasks/abc100_d?lang=en".to_string()));
+    assert_eq!(env.read("abc-100/tests/a/a_1.input"), "5 4\n");
+
+    let output = env.run(&[
+        "--lang",
+        "ja",
+        "-u",
+        &server.url("/contests/tricky"),
+        "-t",
+        "s-abc",
+    ]);
+    assert!(output.status.success());
+    assert!(!env.path("tricky/tests/c/c_1.input").exists());
+    assert!(env.path("tricky/tests/b/b_1.input").exists());
+}
+
+#[test]
+fn whoami_reports_user_of_saved_session() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "fixture";</script>"#),
+    );
+    let env = Env::new(&server);
+    // 2100-01-01T00:00:00Z まで有効
+    save_cookie(&env, "REVEL_SESSION=sig-%00_TS%3A4102444800%00");
+
+    let output = env
+        .command()
+        .env("TZ", "UTC")
+        .arg("whoami")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Logged in as fixture"));
+    assert!(stdout.contains("Session expires at 2100-01-01 00:00:00"));
+    assert_eq!(
+        server.requests()[0].header("cookie"),
+        Some("REVEL_SESSION=sig-%00_TS%3A4102444800%00")
+    );
+}
+
+#[test]
+fn whoami_fails_when_session_has_expired() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "";</script>"#),
+    );
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=sig-%00_TS%3A1000000000%00");
+
+    let output = env
+        .command()
+        .env("TZ", "UTC")
+        .arg("whoami")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Session has Expired at 2001-09-09 01:46:40"));
+
+    let env = Env::new(&server);
+    let output = env.run(&["whoami"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Not Logged in"));
+}
+
+#[test]
+fn login_imports_session_from_cookie_file() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "fixture";</script>"#),
+    );
+    let env = Env::new(&server);
+    std::fs::write(
+        env.path("cookies.txt"),
+        "# Netscape HTTP Cookie File\n\
+         .example.com\tTRUE\t/\tFALSE\t4102444800\tREVEL_SESSION\tother-site\n\
+         #HttpOnly_127.0.0.1\tFALSE\t/\tTRUE\t4102444800\tREVEL_SESSION\tfrom-browser\n\
+         127.0.0.1\tFALSE\t/\tFALSE\t4102444800\tlanguage\tja\n",
+    )
+    .unwrap();
+
+    let output = env.run(&["login", "--cookie-file", "cookies.txt"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Logged in as fixture"));
+    let saved = env.home.path().join(".atcoder-create-contest-dir/cookie");
+    let jar = std::fs::read_to_string(&saved).unwrap();
+    assert!(jar.contains("name = 'REVEL_SESSION'\nvalue = 'from-browser'\ndomain = '127.0.0.1'"));
+    assert!(jar.contains("expires = '2100-01-01T00:00:00+00:00'"));
+    assert!(!jar.contains("other-site"));
+    assert_eq!(
+        server.requests()[0].header("cookie"),
+        Some("REVEL_SESSION=from-browser")
+    );
+
+    // 値だけを書いたファイルも受け付ける
+    std::fs::write(env.path("session.txt"), "raw-value\n").unwrap();
+    let output = env.run(&["login", "--cookie-file", "session.txt"]);
+    assert!(output.status.success());
+    assert!(std::fs::read_to_string(&saved)
+        .unwrap()
+        .contains("value = 'raw-value'"));
+}
+
+#[test]
+fn login_rejects_invalid_cookie_file() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "";</script>"#),
+    );
+    let env = Env::new(&server);
+    std::fs::write(env.path("session.txt"), "expired-value").unwrap();
+
+    let output = env.run(&["login", "--cookie-file", "session.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cookie is not Valid"));
+    assert!(!env
+        .home
+        .path()
+        .join(".atcoder-create-contest-dir/cookie")
+        .exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn saved_cookie_is_private_and_can_be_encrypted() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "fixture";</script>"#),
+    );
+    let env = Env::new(&server);
+    std::fs::write(env.path("session.txt"), "secret-session").unwrap();
+    let saved = env.home.path().join(".atcoder-create-contest-dir/cookie");
+
+    let output = env.run(&["login", "--cookie-file", "session.txt"]);
+    assert!(output.status.success());
+    let mode = std::fs::metadata(&saved).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    let output = env
+        .command()
+        .env("ATCODER_COOKIE_PASSPHRASE", "correct horse")
+        .args(["login", "--cookie-file", "session.txt", "--encrypt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let content = std::fs::read_to_string(&saved).unwrap();
+    assert!(content.contains("[encrypted]"));
+    assert!(!content.contains("secret-session"));
+    let mode = std::fs::metadata(&saved).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    let output = env
+        .command()
+        .env("ATCODER_COOKIE_PASSPHRASE", "correct horse")
+        .arg("whoami")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let requests = server.requests();
+    assert_eq!(
+        requests.last().unwrap().header("cookie"),
+        Some("REVEL_SESSION=secret-session")
+    );
+
+    let output = env
+        .command()
+        .env("ATCODER_COOKIE_PASSPHRASE", "wrong")
+        .arg("whoami")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("wrong passphrase?"));
+}
+
+#[test]
+fn all_lines_of_legacy_cookie_file_are_sent() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    save_cookie(
+        &env,
+        "REVEL_SESSION=session\nREVEL_FLASH=flash;language=ja\n",
+    );
+
+    let output = env.run(&["-u", &server.url("/contests/abc100")]);
+    assert!(output.status.success());
+    assert!(server.requests().iter().all(
+        |r| r.header("cookie") == Some("REVEL_SESSION=session; REVEL_FLASH=flash; language=ja")
+    ));
+}
+
+#[test]
+fn profiles_keep_separate_sessions() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "practice_account";</script>"#),
+    );
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=main-session");
+    std::fs::write(env.path("session.txt"), "practice-session").unwrap();
+
+    let output = env.run(&[
+        "login",
+        "--profile",
+        "practice",
+        "--cookie-file",
+        "session.txt",
+    ]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Logged in as practice_account (profile `practice`)"));
+    let data_dir = env.home.path().join(".atcoder-create-contest-dir");
+    assert!(
+        std::fs::read_to_string(data_dir.join("profiles/practice/cookie"))
+            .unwrap()
+            .contains("practice-session")
+    );
+    assert_eq!(
+        std::fs::read_to_string(data_dir.join("cookie")).unwrap(),
+        "REVEL_SESSION=main-session"
+    );
+
+    let output = env.run(&["--profile", "practice", "whoami"]);
+    assert!(output.status.success());
+    assert_eq!(
+        server.requests().last().unwrap().header("cookie"),
+        Some("REVEL_SESSION=practice-session")
+    );
+    let output = env.run(&["whoami"]);
+    assert!(output.status.success());
+    assert_eq!(
+        server.requests().last().unwrap().header("cookie"),
+        Some("REVEL_SESSION=main-session")
+    );
+
+    let output = env.run(&["--profile", "other", "whoami"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("run `create-contest --profile other login`"));
+
+    let output = env.run(&["--profile", "../main", "whoami"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid Profile Name"));
+}
+
+#[test]
+fn logout_invalidates_session_and_removes_cookie() {
+    let server = FixtureServer::start("atcoder");
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(
+            r#"<script>var csrfToken = "token+/="; var userScreenName = "fixture";</script>"#,
+        ),
+    );
+    server.route("POST", "/logout", FixtureResponse::ok(""));
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=session");
+
+    let output = env.run(&["logout"]);
+    assert!(output.status.success());
+    let logout = server
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST")
+        .unwrap();
+    assert_eq!(logout.path, "/logout");
+    assert_eq!(logout.body, "csrf_token=token%2B%2F%3D");
+    assert_eq!(logout.header("cookie"), Some("REVEL_SESSION=session"));
+    assert!(!env
+        .home
+        .path()
+        .join(".atcoder-create-contest-dir/cookie")
+        .exists());
+
+    let output = env.run(&["logout"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Not Logged in"));
+}
+
+/// ログインページ、ログインの POST、ログイン後の `/home` を用意する
+fn route_login(server: &FixtureServer) {
+    server.route(
+        "GET",
+        "/login",
+        FixtureResponse::ok(r#"<form><input type="hidden" name="csrf_token" value="csrf"></form>"#)
+            .header("Set-Cookie", "REVEL_SESSION=guest; Path=/"),
+    );
+    server.route(
+        "POST",
+        "/login",
+        FixtureResponse::ok("").header("Set-Cookie", "REVEL_SESSION=logged-in; Path=/"),
+    );
+    // ログインしていなければ AtCoder と同じく空
+    server.route(
+        "GET",
+        "/home",
+        FixtureResponse::ok(r#"<script>var userScreenName = "";</script>"#),
+    );
+    server.route_with_cookie(
+        "GET",
+        "/home",
+        "REVEL_SESSION=logged-in",
+        FixtureResponse::ok(r#"<script>var userScreenName = "fixture";</script>"#),
+    );
+}
+
+fn login_body(server: &FixtureServer) -> String {
+    server
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST" && r.path == "/login")
+        .unwrap()
+        .body
+}
+
+#[test]
+fn login_reads_password_without_tty() {
+    let server = FixtureServer::start("atcoder");
+    route_login(&server);
+
+    // 環境変数
+    let env = Env::new(&server);
+    let output = env
+        .command()
+        .env("ATCODER_USERNAME", "fixture")
+        .env("ATCODER_PASSWORD", "from-env")
+        .arg("login")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Logged in as fixture"));
+    let body = login_body(&server);
+    assert!(body.contains("username=fixture"));
+    assert!(body.contains("password=from-env"));
+    let cookie =
+        std::fs::read_to_string(env.home.path().join(".atcoder-create-contest-dir/cookie"))
+            .unwrap();
+    assert!(cookie.contains("logged-in"));
+    let home = server
+        .requests()
+        .into_iter()
+        .find(|r| r.path == "/home")
+        .unwrap();
+    assert_eq!(home.header("cookie"), Some("REVEL_SESSION=logged-in"));
+
+    // --password-stdin (環境変数より優先する)
+    let server = FixtureServer::start("atcoder");
+    route_login(&server);
+    let env = Env::new(&server);
+    let mut child = env
+        .command()
+        .env("ATCODER_PASSWORD", "from-env")
+        .args(["login", "-u", "fixture", "--password-stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"from-stdin\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let body = login_body(&server);
+    assert!(body.contains("password=from-stdin"));
+    assert!(!body.contains("from-env"));
+}
+
+#[cfg(unix)]
+#[test]
+fn login_reads_private_credentials_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = FixtureServer::start("atcoder");
+    route_login(&server);
+    let env = Env::new(&server);
+    let credentials = env.path("credentials.toml");
+    std::fs::write(
+        &credentials,
+        "username = \"fixture\"\npassword = \"from-file\"\n",
+    )
+    .unwrap();
+
+    std::fs::set_permissions(&credentials, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let output = env.run(&["login", "--credentials", "credentials.toml"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("chmod 600"));
+    assert!(server.requests().iter().all(|r| r.method != "POST"));
+
+    std::fs::set_permissions(&credentials, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let output = env.run(&["login", "--credentials", "credentials.toml"]);
+    assert!(output.status.success());
+    let body = login_body(&server);
+    assert!(body.contains("username=fixture"));
+    assert!(body.contains("password=from-file"));
+}
+
+#[test]
+fn login_is_not_retried() {
+    let server = FixtureServer::start("atcoder");
+    route_login(&server);
+    server.route("POST", "/login", FixtureResponse::status(503));
+    let env = Env::new(&server);
+
+    // `Env::command` は `--retries 0` なので、再試行する設定で直接起動する
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_create-contest"))
+        .current_dir(env.work.path())
+        .env("HOME", env.home.path())
+        .env("ATCODER_BASE_URL", &env.base_url)
+        .env("ATCODER_USERNAME", "fixture")
+        .env("ATCODER_PASSWORD", "from-env")
+        .args(["--interval", "0", "--retries", "2", "login"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let posts = server
+        .requests()
+        .into_iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+/// AHC で配布されるものと同じく、`tools/` の中に `files` を入れた zip
+fn tools_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, content) in files {
+        zip.start_file(format!("tools/{}", name), Default::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn ahc_dir_has_tools_inputs_and_aliases() {
+    let server = FixtureServer::start("atcoder");
+    // 問題文のリンク先は AtCoder とは別のホスト
+    let tools_server = FixtureServer::start("atcoder");
+    tools_server.route(
+        "GET",
+        "/img/ahc001/tools.zip",
+        FixtureResponse::ok(tools_zip(&[
+            ("Cargo.toml", "[package]\nname = \"tools\"\n"),
+            ("src/bin/gen.rs", "fn main() {}\n"),
+            ("src/bin/vis.rs", "fn main() {}\n"),
+            ("seeds.txt", "0\n1\n"),
+            ("in/0000.txt", "2\n0 0 1\n1 1 1\n"),
+        ])),
+    );
+    let task_page = std::fs::read_to_string(
+        common::fixtures_dir().join("atcoder/contests/ahc001/tasks/ahc001_a.html"),
+    )
+    .unwrap()
+    .replace(
+        "href=\"/img/ahc001/tools.zip\"",
+        &format!("href=\"{}\"", tools_server.url("/img/ahc001/tools.zip")),
+    );
+    server.route(
+        "GET",
+        "/contests/ahc001/tasks/ahc001_a",
+        FixtureResponse::ok(task_page),
+    );
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=fixture-session");
+
+    let output = env.run(&["-u", &server.url("/contests/ahc001")]);
+    assert!(output.status.success());
+
+    // 保存した cookie は AtCoder にだけ送る
+    assert!(server
+        .requests()
+        .iter()
+        .all(|r| r.header("cookie") == Some("REVEL_SESSION=fixture-session")));
+    let tools_requests = tools_server.requests();
+    assert_eq!(tools_requests.len(), 1);
+    assert_eq!(tools_requests[0].header("cookie"), None);
+
+    let solution = env.read("ahc-001/src/a.rs");
+    assert!(solution.starts_with("//! A - AtCoder Ad\n"));
+    assert!(solution.contains("        n: usize,\n"));
+    assert!(env
+        .read("ahc-001/statements/a.md")
+        .contains("Place $n$ rectangles."));
+    assert!(!env.path("ahc-001/tests/a").exists());
+    assert!(env.read("ahc-001/contest.toml").contains("kind = 'ahc'"));
+
+    assert_eq!(env.read("ahc-001/tools/seeds.txt"), "0\n1\n");
+    assert!(env.path("ahc-001/tools/src/bin/gen.rs").is_file());
+    assert_eq!(env.read("ahc-001/in/0000.txt"), "2\n0 0 1\n1 1 1\n");
+
+    let aliases = env.read("ahc-001/.cargo/config.toml");
+    assert!(aliases.contains("a = \"run-a\""));
+    assert!(aliases.contains(
+        "gen = \"run --release --manifest-path tools/Cargo.toml --bin gen -- tools/seeds.txt\""
+    ));
+    assert!(
+        aliases.contains("vis = \"run --release --manifest-path tools/Cargo.toml --bin vis --\"")
+    );
+    assert!(!aliases.contains("tester"));
+}
+
+#[test]
+fn ahc_tools_can_be_given_as_directory() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    std::fs::create_dir_all(env.path("local-tools/src/bin")).unwrap();
+    std::fs::write(env.path("local-tools/Cargo.toml"), "[package]\n").unwrap();
+    std::fs::write(env.path("local-tools/src/bin/tester.rs"), "fn main() {}\n").unwrap();
+
+    let output = env.run(&[
+        "-u",
+        &server.url("/contests/ahc001"),
+        "--tools",
+        "local-tools",
+    ]);
+    assert!(output.status.success());
+    assert!(server.requests().iter().all(|r| !r.path.ends_with(".zip")));
+    assert!(env.path("ahc-001/tools/src/bin/tester.rs").is_file());
+    assert!(env.path("ahc-001/in").is_dir());
+    assert!(env
+        .read("ahc-001/.cargo/config.toml")
+        .contains("tester = \"run --release --manifest-path tools/Cargo.toml --bin tester --\""));
+
+    let output = env.run(&["-n", "abc-300", "--tools", "local-tools"]);
+    assert!(!output.status.success());
+}
+
+/// 依存クレートなしでビルドできる、AHC のディレクトリ
+///
+/// 解答は入力の数を 10 倍して出力し、3 では止まらず、2 では異常終了する
+/// `vis` は出力をそのままスコアにする
+fn write_heuristic_dir(env: &Env) {
+    let files = [
+        (
+            "Cargo.toml",
+            "[package]\nname = \"ahc-test\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n[[bin]]\nname = \"a\"\npath = \"src/a.rs\"\n",
+        ),
+        (
+            "src/a.rs",
+            r#"fn main() {
+    let mut s = String::new();
+    std::io::stdin().read_line(&mut s).unwrap();
+    let n: i64 = s.trim().parse().unwrap();
+    match n {
+        2 => std::process::exit(1),
+        3 => std::thread::sleep(std::time::Duration::from_secs(60)),
+        _ => println!("{}", n * 10),
+    }
+}
+"#,
+        ),
+        (
+            "contest.toml",
+            "contest_id = 'ahc001'\nkind = 'ahc'\n\n[[problems]]\nlabel = 'a'\ntime_limit_ms = 2000\n",
+        ),
+        (
+            "tools/Cargo.toml",
+            "[package]\nname = \"tools\"\nversion = \"0.1.0\"\nedition = \"2018\"\n",
+        ),
+        (
+            "tools/src/bin/vis.rs",
+            r#"fn main() {
+    let output = std::env::args().nth(2).unwrap();
+    let output = std::fs::read_to_string(output).unwrap();
+    println!("Score = {}", output.trim());
+}
+"#,
+        ),
+    ];
+    for (path, content) in files.iter() {
+        let path = env.path(&format!("ahc-test/{}", path));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    for seed in 0..5 {
+        let path = env.path(&format!("ahc-test/in/{:04}.txt", seed));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("{}\n", seed)).unwrap();
+    }
+}
+
+#[test]
+fn score_runs_seeds_in_parallel_and_saves_csv() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    write_heuristic_dir(&env);
+
+    let output = env.run_in(
+        env.path("ahc-test"),
+        &[
+            "score",
+            "--seeds",
+            "0..5",
+            "--jobs",
+            "4",
+            "--time-limit",
+            "1",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0004: 40 ("));
+    assert!(stdout.contains("0002: RE ("));
+    assert!(stdout.contains("0003: TLE ("));
+    assert!(stdout.contains("Seeds: 5 (OK: 3, TLE: 1, RE: 1, ERR: 0)"));
+    assert!(stdout.contains("Total: 50\n"));
+    assert!(stdout.contains("Mean:  10.00\n"));
+    assert!(stdout.contains("Min:   0 (seed 0000)"));
+    assert!(stdout.contains("Max:   40 (seed 0004)"));
+    assert_eq!(env.read("ahc-test/out/0001.txt"), "10\n");
+
+    let results = std::fs::read_dir(env.path("ahc-test/results"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let csv = std::fs::read_to_string(&results[0]).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "seed,status,score,time_ms");
+    assert!(lines[1].starts_with("0,OK,0,"));
+    assert!(lines[3].starts_with("2,RE,,"));
+    assert!(lines[4].starts_with("3,TLE,,"));
+    assert!(lines[5].starts_with("4,OK,40,"));
+
+    let output = env.run_in(env.path("ahc-test"), &["score", "--seeds", "5"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cargo gen"));
+}
+
+#[test]
+fn score_keeps_best_per_seed_and_compare_shows_changes() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    write_heuristic_dir(&env);
+    let dir = env.path("ahc-test");
+    let set_factor = |factor: i64| {
+        let path = dir.join("src/a.rs");
+        let content = std::fs::read_to_string(&path).unwrap();
+        let content = content
+            .lines()
+            .map(
+                |line| match line.trim_start().starts_with("_ => println!") {
+                    true => format!("        _ => println!(\"{{}}\", n * {}),", factor),
+                    false => line.to_string(),
+                },
+            )
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, content).unwrap();
+    };
+    let score = || {
+        let output = env.run_in(&dir, &["score", "--seeds", "0..2"]);
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = score();
+    assert!(stdout.contains("New Best: 2 seeds\n  0000: 0\n  0001: 10\n"));
+    set_factor(20);
+    let stdout = score();
+    assert!(stdout.contains("New Best: 1 seeds\n  0001: 10 -> 20\n"));
+
+    let history = std::fs::read_to_string(dir.join("results/history.toml")).unwrap();
+    assert_eq!(history.matches("[[runs]]").count(), 2);
+    assert!(history.contains("score = 20\nsource = 'sha256:"));
+
+    let output = env.run_in(&dir, &["compare"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("with best\n"));
+    assert!(stdout.contains("0001: 10 -> 20 (+10, +100.00%) improved"));
+    assert!(stdout.contains("Improved: 1, Regressed: 0, Unchanged: 1"));
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+
+    let output = env.run_in(&dir, &["compare", "--previous"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0001: 10 -> 20 (+10, +100.00%) improved"));
+    assert!(stdout.contains("Improved: 1, Regressed: 0, Unchanged: 1"));
+    assert!(stdout.contains("Total: 10 -> 20 (+10)"));
+
+    set_factor(5);
+    let output = env.run_in(&dir, &["compare"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("has Changed since the Last Run"));
+
+    // スコアが小さいほど良い問題
+    let metadata = std::fs::read_to_string(dir.join("contest.toml")).unwrap();
+    std::fs::write(
+        dir.join("contest.toml"),
+        metadata.replace("kind = 'ahc'\n", "kind = 'ahc'\nminimize = true\n"),
+    )
+    .unwrap();
+    let stdout = score();
+    assert!(stdout.contains("New Best: 1 seeds\n  0001: 20 -> 5\n"));
+    let output = env.run_in(&dir, &["compare", "--previous"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0001: 20 -> 5 (-15, -75.00%) improved"));
+}
+
+#[test]
+fn batch_creates_each_contest_in_the_range() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+
+    // abc099 は fixture がないので失敗し、`..` は終端の abc101 を含まない
+    let output = env.run(&["batch", "abc099..abc101", "-j", "1"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Succeeded: 1\n  abc-100\n"));
+    assert!(stdout.contains("Failed: 1\n  abc-099 "));
+    assert_eq!(env.read("abc-100/tests/a/a_1.input"), "5 4\n");
+    assert!(server
+        .requests()
+        .iter()
+        .all(|r| !r.path.starts_with("/contests/abc101")));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::{save_cookie, Env, FixtureServer};
+
+#[test]
+fn create_from_codeforces_url_fetches_samples() {
+    let server = FixtureServer::start("codeforces");
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=fixture-session");
+
+    let output = env
+        .command()
+        .env("ATCODER_BASE_URL", "https://atcoder.invalid")
+        .env("CODEFORCES_BASE_URL", &server.base_url)
+        .args(["-u", &server.url("/contest/1234/problem/B")])
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        eprintln!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    }
+    assert!(output.status.success());
+
+    // 問題の数と名前は問題の一覧の label から決める
+    for problem in ["a", "b", "c1"] {
+        assert!(env.path(&format!("cf-1234/src/{}.rs", problem)).is_file());
+        assert!(env.path(&format!("cf-1234/tests/{}.rs", problem)).is_file());
+    }
+    assert!(!env.path("cf-1234/src/d.rs").exists());
+    assert!(env.read("cf-1234/Cargo.toml").contains("name = \"c1\""));
+
+    assert_eq!(
+        env.read("cf-1234/tests/a/a_1.input"),
+        "3\n5\n1 2 3 4 5\n3\n1 2 2\n4\n1 1 1 1\n"
+    );
+    assert_eq!(env.read("cf-1234/tests/a/a_1.output"), "3\n2\n1\n");
+    assert_eq!(
+        env.read("cf-1234/tests/b/b_1.input"),
+        "7 2\n1 2 3 2 1 3 2\n"
+    );
+    assert_eq!(env.read("cf-1234/tests/b/b_2.output"), "3\n1 3 2\n");
+    assert_eq!(
+        env.read("cf-1234/tests/c1/c1_1.input"),
+        "1\n7\n2323216\n1615124\n"
+    );
+    assert!(env.read("cf-1234/tests/b.rs").contains("fn sample_b_2()"));
+
+    let metadata = env.read("cf-1234/contest.toml");
+    assert!(metadata.contains("contest_id = '1234'"));
+    assert!(metadata.contains("kind = 'cf'"));
+    assert!(metadata.contains(&format!("url = '{}'", server.url("/contest/1234"))));
+    assert!(metadata.contains("title = 'Pipes (Easy Version)'"));
+    assert!(metadata.contains("time_limit_ms = 2500"));
+    assert!(metadata.contains("memory_limit_mb = 512"));
+
+    // AtCoder の cookie は送らない
+    let requests = server.requests();
+    assert!(!requests.is_empty());
+    assert!(requests.iter().all(|r| r.header("cookie").is_none()));
+}
@@ -0,0 +1,308 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// `tests/fixtures/<judge>` 以下の HTML を返すだけの、ジャッジの代わりのローカルサーバー
+///
+/// `GET /contests/abc100/tasks` は `contests/abc100/tasks.html` を返す
+/// POST などは `route` で登録したレスポンスを返す
+pub struct FixtureServer {
+    pub base_url: String,
+    routes: Arc<Mutex<Routes>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+/// `(method, path)` ごとのレスポンスと、`Cookie` に値が含まれるときだけ返すレスポンス
+#[derive(Default)]
+struct Routes {
+    always: HashMap<(String, String), FixtureResponse>,
+    with_cookie: HashMap<(String, String), (String, FixtureResponse)>,
+}
+
+#[derive(Clone)]
+pub struct FixtureResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+impl FixtureResponse {
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        FixtureResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        FixtureResponse {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl FixtureServer {
+    pub fn start(judge: &str) -> Self {
+        let root = fixtures_dir().join(judge);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes = Arc::new(Mutex::new(Routes::default()));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let routes = Arc::clone(&routes);
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let root = root.clone();
+                    let routes = Arc::clone(&routes);
+                    let requests = Arc::clone(&requests);
+                    thread::spawn(move || handle(stream, &root, &routes, &requests));
+                }
+            });
+        }
+
+        FixtureServer {
+            base_url,
+            routes,
+            requests,
+        }
+    }
+
+    pub fn route(&self, method: &str, path: &str, response: FixtureResponse) {
+        self.routes
+            .lock()
+            .unwrap()
+            .always
+            .insert((method.to_string(), path.to_string()), response);
+    }
+
+    /// リクエストの `Cookie` に `cookie` (`REVEL_SESSION=logged-in` など) が含まれるときだけ `response` を返す
+    pub fn route_with_cookie(
+        &self,
+        method: &str,
+        path: &str,
+        cookie: &str,
+        response: FixtureResponse,
+    ) {
+        self.routes.lock().unwrap().with_cookie.insert(
+            (method.to_string(), path.to_string()),
+            (cookie.to_string(), response),
+        );
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    root: &Path,
+    routes: &Mutex<Routes>,
+    requests: &Mutex<Vec<Request>>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            headers.push((k.trim().to_string(), v.trim().to_string()));
+        }
+    }
+    let content_length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    let request = Request {
+        method: method.clone(),
+        path: path.clone(),
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+    let if_none_match = request.header("if-none-match").map(|v| v.to_string());
+    let has_cookie = |cookie: &str| {
+        request
+            .header("cookie")
+            .is_some_and(|v| v.split(';').any(|c| c.trim() == cookie))
+    };
+
+    let key = (method.clone(), path.clone());
+    let route = {
+        let routes = routes.lock().unwrap();
+        match routes.with_cookie.get(&key) {
+            Some((cookie, response)) if has_cookie(cookie) => Some(response.clone()),
+            _ => routes.always.get(&key).cloned(),
+        }
+    };
+    requests.lock().unwrap().push(request);
+    let response = route.unwrap_or_else(|| {
+        // `?lang=en` などのクエリつきのファイルがなければクエリなしのファイルを返す
+        let file = root.join(format!("{}.html", fixture_name(&path)));
+        let fallback = root.join(format!(
+            "{}.html",
+            fixture_name(path.split('?').next().unwrap())
+        ));
+        match std::fs::read_to_string(file).or_else(|_| std::fs::read_to_string(fallback)) {
+            Ok(body) if method == "GET" => {
+                let etag = format!("\"{:x}\"", fnv1a(&body));
+                if if_none_match.as_deref() == Some(etag.as_str()) {
+                    FixtureResponse::status(304).header("ETag", &etag)
+                } else {
+                    FixtureResponse::ok(body).header("ETag", &etag)
+                }
+            }
+            _ => FixtureResponse::status(404),
+        }
+    });
+
+    let mut head = format!(
+        "HTTP/1.1 {} Fixture\r\nContent-Length: {}\r\nContent-Type: text/html; charset=utf-8\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (k, v) in &response.headers {
+        head += &format!("{}: {}\r\n", k, v);
+    }
+    head += "\r\n";
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+}
+
+/// ETag 用の簡単なハッシュ (FNV-1a)
+fn fnv1a(body: &str) -> u64 {
+    body.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// `/contests/abc100/tasks?lang=en` -> `contests/abc100/tasks.lang=en`
+fn fixture_name(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    let (path, query) = match path.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (path, None),
+    };
+    let path = if path.is_empty() { "index" } else { path };
+    match query {
+        Some(query) => format!("{}.{}", path, query),
+        None => path.to_string(),
+    }
+}
+
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// 一時的な HOME と作業ディレクトリで `create-contest` を実行するための環境
+pub struct Env {
+    pub home: tempfile::TempDir,
+    pub work: tempfile::TempDir,
+    pub base_url: String,
+}
+impl Env {
+    pub fn new(server: &FixtureServer) -> Self {
+        Env {
+            home: tempfile::tempdir().unwrap(),
+            work: tempfile::tempdir().unwrap(),
+            base_url: server.base_url.clone(),
+        }
+    }
+
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_create-contest"));
+        command
+            .current_dir(self.work.path())
+            .env("HOME", self.home.path())
+            .env("ATCODER_BASE_URL", &self.base_url)
+            .env("RUST_BACKTRACE", "0")
+            .args(["--interval", "0", "--retries", "0"]);
+        command
+    }
+
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.run_in(self.work.path(), args)
+    }
+
+    pub fn run_in(&self, dir: impl AsRef<Path>, args: &[&str]) -> Output {
+        let output = self
+            .command()
+            .current_dir(dir.as_ref())
+            .args(args)
+            .output()
+            .unwrap();
+        if !output.status.success() {
+            eprintln!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+            eprintln!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        }
+        output
+    }
+
+    pub fn path(&self, path: &str) -> PathBuf {
+        self.work.path().join(path)
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.path(path))
+            .unwrap_or_else(|e| panic!("failed to read {}: {}", path, e))
+    }
+}
+
+/// 保存した cookie (1 行ずつの古い形式)
+pub fn save_cookie(env: &Env, cookie: &str) {
+    let data_dir = env.home.path().join(".atcoder-create-contest-dir");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    std::fs::write(data_dir.join("cookie"), cookie).unwrap();
+}
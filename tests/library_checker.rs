@@ -0,0 +1,118 @@
+mod common;
+
+use std::path::Path;
+
+use common::{Env, FixtureServer};
+
+/// `generate.py -p NAME` で入力・出力と checker を作るだけの library-checker-problems
+fn write_problems_repo(repo: &Path) {
+    let problem_dir = repo.join("datastructure/unionfind");
+    std::fs::create_dir_all(&problem_dir).unwrap();
+    std::fs::write(
+        problem_dir.join("info.toml"),
+        "title = 'Unionfind'\ntimelimit = 5.0\n\n[[tests]]\nname = 'example.in'\nnumber = 1\n",
+    )
+    .unwrap();
+    std::fs::write(
+        problem_dir.join("task.md"),
+        "## @{keyword.statement}\n\n$N$ 頂点 $0$ 辺のグラフに辺を追加していきます。\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo.join("generate.py"),
+        r#"import argparse, os, pathlib
+
+parser = argparse.ArgumentParser()
+parser.add_argument('-p', '--problem', nargs='*', required=True)
+args = parser.parse_args()
+for name in args.problem:
+    matches = list(pathlib.Path('.').glob('*/' + name + '/info.toml'))
+    if not matches:
+        raise SystemExit('problem not found: ' + name)
+    problem = matches[0].parent
+    (problem / 'in').mkdir(exist_ok=True)
+    (problem / 'out').mkdir(exist_ok=True)
+    (problem / 'in' / 'example_00.in').write_text('4 2\n0 0 1\n1 0 1\n')
+    (problem / 'out' / 'example_00.out').write_text('1\n')
+    (problem / 'in' / 'random_00.in').write_text('2 1\n1 0 1\n')
+    (problem / 'out' / 'random_00.out').write_text('0\n')
+    checker = problem / 'checker'
+    checker.write_text('#!/bin/sh\ncmp -s "$2" "$3"\n')
+    os.chmod(checker, 0o755)
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn library_checker_problem_is_created_from_local_repo() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    // 文字列リテラルにするときにエスケープが必要なパス
+    let repo = tempfile::Builder::new()
+        .prefix("problems \"lc\" \\")
+        .tempdir()
+        .unwrap();
+    write_problems_repo(repo.path());
+
+    let output = env.run(&[
+        "library-checker",
+        "unionfind",
+        "--repo",
+        repo.path().to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+
+    let problem_dir = repo
+        .path()
+        .canonicalize()
+        .unwrap()
+        .join("datastructure/unionfind");
+    assert!(problem_dir.join("in/example_00.in").is_file());
+
+    let test_file = env.read("lc-unionfind/tests/a.rs");
+    assert!(test_file.contains(&format!(
+        "const PROBLEM_DIR: &'static str = {:?};",
+        problem_dir.to_string_lossy()
+    )));
+    assert!(test_file.contains("fn case_example_00() {\n    test_base(\"a\", \"example_00\");"));
+    assert!(test_file.contains("fn case_random_00()"));
+    assert!(test_file.contains("format!(\"{}/checker\", PROBLEM_DIR)"));
+
+    let metadata = env.read("lc-unionfind/contest.toml");
+    assert!(metadata.contains("contest_id = 'unionfind'"));
+    assert!(metadata.contains("kind = 'lc'"));
+    assert!(metadata.contains("title = 'Unionfind'"));
+    assert!(metadata.contains("time_limit_ms = 5000"));
+    assert!(metadata.contains("sample_count = 2"));
+
+    assert!(env
+        .read("lc-unionfind/statements/a.md")
+        .contains("$N$ 頂点 $0$ 辺のグラフに辺を追加していきます。"));
+    assert!(env
+        .read("lc-unionfind/src/a.rs")
+        .starts_with("//! A - Unionfind\n"));
+
+    // ネットワークには接続しない
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn library_checker_fails_on_unknown_problem() {
+    let server = FixtureServer::start("atcoder");
+    let env = Env::new(&server);
+    let repo = tempfile::tempdir().unwrap();
+    write_problems_repo(repo.path());
+
+    let output = env.run(&[
+        "library-checker",
+        "no_such_problem",
+        "--repo",
+        repo.path().to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Problem `no_such_problem` is not Found")
+    );
+    assert!(!env.path("lc-no_such_problem").exists());
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use std::process::Output;
+
+use common::{save_cookie, Env, FixtureServer};
+
+fn run_yukicoder(env: &Env, server: &FixtureServer, args: &[&str]) -> Output {
+    let output = env
+        .command()
+        .env("ATCODER_BASE_URL", "https://atcoder.invalid")
+        .env("YUKICODER_BASE_URL", &server.base_url)
+        .args(args)
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        eprintln!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    }
+    output
+}
+
+#[test]
+fn create_from_yukicoder_contest_fetches_samples_and_limits() {
+    let server = FixtureServer::start("yukicoder");
+    let env = Env::new(&server);
+    save_cookie(&env, "REVEL_SESSION=fixture-session");
+
+    let output = run_yukicoder(&env, &server, &["-u", &server.url("/contests/300")]);
+    assert!(output.status.success());
+
+    assert!(env.path("yuki-300/src/b.rs").is_file());
+    assert!(!env.path("yuki-300/src/c.rs").exists());
+    assert_eq!(env.read("yuki-300/tests/a/a_1.input"), "2 3\n");
+    assert_eq!(env.read("yuki-300/tests/a/a_1.output"), "8\n");
+    assert_eq!(env.read("yuki-300/tests/a/a_2.input"), "10 0\n");
+    assert_eq!(env.read("yuki-300/tests/a/a_2.output"), "10\n");
+    assert_eq!(env.read("yuki-300/tests/b/b_1.input"), "2\nab\ncd\n");
+
+    let metadata = env.read("yuki-300/contest.toml");
+    assert!(metadata.contains("contest_id = '300'"));
+    assert!(metadata.contains("kind = 'yuki'"));
+    assert!(metadata.contains(&format!("url = '{}'", server.url("/problems/no/1582"))));
+    assert!(metadata.contains("time_limit_ms = 1500"));
+    assert!(metadata.contains("memory_limit_mb = 256"));
+
+    // AtCoder の cookie は送らない
+    assert!(server
+        .requests()
+        .iter()
+        .all(|r| r.header("cookie").is_none()));
+}
+
+#[test]
+fn create_from_yukicoder_problem_url_makes_single_problem_dir() {
+    let server = FixtureServer::start("yukicoder");
+    let env = Env::new(&server);
+
+    let output = run_yukicoder(&env, &server, &["-u", &server.url("/problems/no/1581")]);
+    assert!(output.status.success());
+
+    assert!(env.path("yuki-no-1581/src/a.rs").is_file());
+    assert!(!env.path("yuki-no-1581/src/b.rs").exists());
+    assert_eq!(env.read("yuki-no-1581/tests/a/a_2.input"), "10 0\n");
+
+    let metadata = env.read("yuki-no-1581/contest.toml");
+    assert!(metadata.contains("contest_id = 'no/1581'"));
+    assert!(metadata.contains("title = 'No.1581 Multiply and Add'"));
+    assert!(metadata.contains("time_limit_ms = 2000"));
+    assert!(metadata.contains("memory_limit_mb = 512"));
+}